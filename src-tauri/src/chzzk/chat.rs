@@ -1,37 +1,127 @@
 use crate::chzzk::types::*;
 use futures_util::{SinkExt, StreamExt};
 use std::sync::Arc;
-use tauri::{AppHandle, Emitter};
+use std::time::Duration;
+use tokio::net::TcpStream;
 use tokio::sync::{mpsc, Mutex};
 use tokio::task::JoinHandle;
-use tokio_tungstenite::{connect_async, tungstenite::Message};
+use tokio_tungstenite::{connect_async, tungstenite::Message, MaybeTlsStream, WebSocketStream};
 use url::Url;
 
+type WsStream = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+const PING_INTERVAL: Duration = Duration::from_secs(20);
+
+// 재연결 정책 (지수 백오프)
+#[derive(Debug, Clone)]
+pub struct ReconnectPolicy {
+    pub initial_delay: Duration,
+    pub max_delay: Duration,
+    pub max_attempts: u32,
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        Self {
+            initial_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(60),
+            max_attempts: 10,
+        }
+    }
+}
+
+impl ReconnectPolicy {
+    // attempt는 1부터 시작하며 시도마다 대기 시간이 두 배로 늘어납니다
+    pub fn delay_for(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        self.initial_delay
+            .saturating_mul(factor)
+            .min(self.max_delay)
+    }
+}
+
+// 치지직 API 주소 (테스트에서는 로컬 목 서버로 교체)
+#[derive(Debug, Clone)]
+pub struct ChzzkEndpoints {
+    pub api_base: String,
+    pub comm_api_base: String,
+    // 없으면 채팅 채널 ID로 kr-ss{n} 서버를 선택
+    pub chat_ws_url: Option<String>,
+}
+
+impl Default for ChzzkEndpoints {
+    fn default() -> Self {
+        Self {
+            api_base: "https://api.chzzk.naver.com".to_string(),
+            comm_api_base: "https://comm-api.game.naver.com".to_string(),
+            chat_ws_url: None,
+        }
+    }
+}
+
+// 세션 종료 원인
+enum SessionEnd {
+    // disconnect()에 의한 정상 종료
+    Closed,
+    // 서버 종료 또는 네트워크 오류
+    Lost(String),
+}
+
+#[derive(Clone)]
 pub struct ChzzkChat {
     channel_id: String,
     chat_channel_id: Option<String>,
     access_token: Option<String>,
-    app_handle: AppHandle,
+    auth: Option<ChzzkAuth>,
+    // 로그인한 경우에만 채팅 전송(SEND) 권한으로 접속
+    user_id_hash: Option<String>,
+    event_tx: mpsc::UnboundedSender<ChatEvent>,
+    endpoints: ChzzkEndpoints,
+    reconnect_policy: ReconnectPolicy,
     is_connected: Arc<Mutex<bool>>,
     ws_task: Arc<Mutex<Option<JoinHandle<()>>>>,
-    ping_task: Arc<Mutex<Option<JoinHandle<()>>>>,
+    watch_task: Arc<Mutex<Option<JoinHandle<()>>>>,
     close_tx: Arc<Mutex<Option<mpsc::Sender<()>>>>,
+    send_tx: Arc<Mutex<Option<mpsc::UnboundedSender<String>>>>,
 }
 
 impl ChzzkChat {
-    pub fn new(channel_id: String, app_handle: AppHandle) -> Self {
+    pub fn new(channel_id: String, event_tx: mpsc::UnboundedSender<ChatEvent>) -> Self {
         Self {
             channel_id,
             chat_channel_id: None,
             access_token: None,
-            app_handle,
+            auth: None,
+            user_id_hash: None,
+            event_tx,
+            endpoints: ChzzkEndpoints::default(),
+            reconnect_policy: ReconnectPolicy::default(),
             is_connected: Arc::new(Mutex::new(false)),
             ws_task: Arc::new(Mutex::new(None)),
-            ping_task: Arc::new(Mutex::new(None)),
+            watch_task: Arc::new(Mutex::new(None)),
             close_tx: Arc::new(Mutex::new(None)),
+            send_tx: Arc::new(Mutex::new(None)),
         }
     }
 
+    pub fn with_auth(mut self, auth: Option<ChzzkAuth>) -> Self {
+        self.auth = auth;
+        self
+    }
+
+    // 테스트에서 목 서버 주소와 짧은 재연결 대기 시간을 주입합니다
+    #[cfg(test)]
+    pub fn with_endpoints(mut self, endpoints: ChzzkEndpoints) -> Self {
+        self.endpoints = endpoints;
+        self
+    }
+
+    #[cfg(test)]
+    pub fn with_reconnect_policy(mut self, reconnect_policy: ReconnectPolicy) -> Self {
+        self.reconnect_policy = reconnect_policy;
+        self
+    }
+
     pub async fn connect(&mut self) -> Result<(), Box<dyn std::error::Error>> {
         println!("Connecting to channel: {}", self.channel_id);
 
@@ -44,8 +134,24 @@ impl ChzzkChat {
             return Err("Channel is not live".into());
         }
 
+        self.connect_live(live_status).await
+    }
+
+    // 방송 중인 채널의 채팅에 연결
+    async fn connect_live(
+        &mut self,
+        live_status: LiveStatus,
+    ) -> Result<(), Box<dyn std::error::Error>> {
         self.chat_channel_id = live_status.chat_channel_id;
 
+        // 로그인 정보가 있으면 채팅 전송용 사용자 ID를 가져옵니다
+        if self.auth.is_some() && self.user_id_hash.is_none() {
+            match self.get_user_id_hash().await.map_err(|e| e.to_string()) {
+                Ok(user_id_hash) => self.user_id_hash = Some(user_id_hash),
+                Err(e) => println!("Failed to get user status, chat is read-only: {}", e),
+            }
+        }
+
         if let Some(chat_channel_id) = &self.chat_channel_id {
             // 채팅 접속 토큰을 가져옵니다
             let token_response = self.get_chat_access_token(chat_channel_id).await?;
@@ -60,20 +166,92 @@ impl ChzzkChat {
         Ok(())
     }
 
-    async fn get_live_status(&self) -> Result<LiveStatus, Box<dyn std::error::Error>> {
+    // 방송이 시작될 때까지 상태를 주기적으로 확인하고, 시작되면 자동으로 연결합니다
+    pub async fn watch(&self, interval: Duration) {
+        println!(
+            "Watching channel {} every {}s",
+            self.channel_id,
+            interval.as_secs()
+        );
+
+        let mut chat = self.clone();
+        let watch_handle = tokio::spawn(async move {
+            chat.run_watch(interval).await;
+        });
+
+        *self.watch_task.lock().await = Some(watch_handle);
+    }
+
+    pub async fn is_watching(&self) -> bool {
+        self.watch_task.lock().await.is_some()
+    }
+
+    async fn run_watch(&mut self, interval: Duration) {
+        let mut is_live = false;
+
+        loop {
+            match self.get_live_status().await.map_err(|e| e.to_string()) {
+                Ok(live_status) => {
+                    let is_open = live_status.status == "OPEN";
+
+                    if is_open && !is_live {
+                        is_live = true;
+                        println!("Stream started on channel {}", self.channel_id);
+                        self.emit_event(ChatEvent::StreamStarted {
+                            live_title: live_status.live_title.clone(),
+                            open_date: live_status.open_date.clone(),
+                        });
+                    } else if !is_open && is_live {
+                        is_live = false;
+                        println!("Stream ended on channel {}", self.channel_id);
+                        self.close_session().await;
+                        self.emit_event(ChatEvent::StreamEnded {
+                            close_date: live_status.close_date.clone(),
+                        });
+                    }
+
+                    // 방송 중인데 채팅 세션이 없으면 (재연결 포기 포함) 다시 연결
+                    if is_live && !self.has_active_session().await {
+                        if let Err(e) = self
+                            .connect_live(live_status)
+                            .await
+                            .map_err(|e| e.to_string())
+                        {
+                            println!("Failed to connect to live chat: {}", e);
+                        }
+                    }
+                }
+                Err(e) => {
+                    println!("Failed to poll live status: {}", e);
+                }
+            }
+
+            tokio::time::sleep(interval).await;
+        }
+    }
+
+    async fn has_active_session(&self) -> bool {
+        self.ws_task
+            .lock()
+            .await
+            .as_ref()
+            .is_some_and(|task| !task.is_finished())
+    }
+
+    pub async fn get_live_status(&self) -> Result<LiveStatus, Box<dyn std::error::Error>> {
         // v1과 v2 둘 다 시도해봅니다
         let urls = vec![
             format!(
-                "https://api.chzzk.naver.com/polling/v2/channels/{}/live-status",
-                self.channel_id
+                "{}/polling/v2/channels/{}/live-status",
+                self.endpoints.api_base, self.channel_id
             ),
             format!(
-                "https://api.chzzk.naver.com/polling/v1/channels/{}/live-status",
-                self.channel_id
+                "{}/polling/v1/channels/{}/live-status",
+                self.endpoints.api_base, self.channel_id
             ),
             format!(
-                "https://api.chzzk.naver.com/service/v2/channels/{}/live-detail",
-                self.channel_id
+                "{}/service/v2/channels/{}/live-detail",
+                self.endpoints.api_base, self.channel_id
             ),
         ];
 
@@ -119,22 +297,27 @@ impl ChzzkChat {
         chat_channel_id: &str,
     ) -> Result<ChatAccessToken, Box<dyn std::error::Error>> {
         let url = format!(
-            "https://comm-api.game.naver.com/nng_main/v1/chats/access-token?channelId={}&chatType=STREAMING",
-            chat_channel_id
+            "{}/nng_main/v1/chats/access-token?channelId={}&chatType=STREAMING",
+            self.endpoints.comm_api_base, chat_channel_id
         );
 
         println!("Fetching chat access token from: {}", url);
 
         let client = reqwest::Client::new();
-        let response = client
+        let mut request = client
             .get(&url)
             .header("User-Agent", "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36")
             .header("Accept", "application/json")
             .header("Accept-Language", "ko-KR,ko;q=0.9,en-US;q=0.8,en;q=0.7")
             .header("Referer", "https://chzzk.naver.com/")
-            .header("Origin", "https://chzzk.naver.com")
-            .send()
-            .await?;
+            .header("Origin", "https://chzzk.naver.com");
+
+        // 채팅 전송 권한이 있는 토큰은 로그인 쿠키가 필요합니다
+        if let Some(auth) = &self.auth {
+            request = request.header("Cookie", auth.cookie_header());
+        }
+
+        let response = request.send().await?;
 
         let status = response.status();
         println!("Response status: {}", status);
@@ -153,18 +336,54 @@ impl ChzzkChat {
         Ok(serde_json::from_value(content)?)
     }
 
-    async fn connect_websocket(&self) -> Result<(), Box<dyn std::error::Error>> {
-        let chat_channel_id = self.chat_channel_id.as_ref().unwrap();
-        let access_token = self.access_token.as_ref().unwrap();
+    async fn get_user_id_hash(&self) -> Result<String, Box<dyn std::error::Error>> {
+        let auth = self.auth.as_ref().ok_or("No auth configured")?;
 
+        let url = format!(
+            "{}/nng_main/v1/user/getUserStatus",
+            self.endpoints.comm_api_base
+        );
+
+        let client = reqwest::Client::new();
+        let response = client
+            .get(&url)
+            .header("User-Agent", "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36")
+            .header("Accept", "application/json")
+            .header("Referer", "https://chzzk.naver.com/")
+            .header("Origin", "https://chzzk.naver.com")
+            .header("Cookie", auth.cookie_header())
+            .send()
+            .await?;
+
+        let data: serde_json::Value = response.json().await?;
+
+        // 쿠키가 만료되었으면 userIdHash가 null로 옵니다
+        data["content"]["userIdHash"]
+            .as_str()
+            .map(|s| s.to_string())
+            .ok_or_else(|| "Not logged in (check NID_AUT/NID_SES cookies)".into())
+    }
+
+    async fn open_websocket(
+        &self,
+        chat_channel_id: &str,
+        access_token: &str,
+    ) -> Result<WsStream, String> {
         // 치지직 채팅은 특별한 WebSocket URL 형식을 사용합니다
-        // 서버 번호를 1-10 중에서 선택
-        let server_id = (chat_channel_id.chars().next().unwrap_or('1') as u8 % 10 + 1) as u8;
+        let base_url = match &self.endpoints.chat_ws_url {
+            Some(url) => url.clone(),
+            None => {
+                // 서버 번호를 1-10 중에서 선택
+                let server_id =
+                    (chat_channel_id.chars().next().unwrap_or('1') as u8 % 10 + 1) as u8;
+                format!("wss://kr-ss{}.chat.naver.com/chat", server_id)
+            }
+        };
 
         // 작동하는 WebSocket URL 형식 사용 (쿼리 파라미터 형식)
         let ws_url = format!(
-            "wss://kr-ss{}.chat.naver.com/chat?cid={}&at={}",
-            server_id,
+            "{}?cid={}&at={}",
+            base_url,
             chat_channel_id,
             urlencoding::encode(access_token)
         );
@@ -177,289 +396,454 @@ impl ChzzkChat {
 
         println!("Connecting to WebSocket URL: {}", ws_url);
 
-        match Url::parse(&ws_url) {
-            Ok(url) => match connect_async(url).await {
-                Ok((ws_stream, _)) => {
-                    println!("Successfully connected to WebSocket!");
-
-                    let (mut write, mut read) = ws_stream.split();
-
-                    // 연결 성공 이벤트 발생 (중복 방지)
-                    {
-                        let mut is_connected = self.is_connected.lock().await;
-                        if !*is_connected {
-                            self.app_handle
-                                .emit("chzzk-chat-event", ChatEvent::Connected)?;
-                            *is_connected = true;
-                            println!("Emitted connected event and set is_connected to true");
-                        } else {
-                            println!("Already connected, skipping connected event emission");
-                        }
-                    }
+        let url = Url::parse(&ws_url).map_err(|e| {
+            println!("Invalid URL: {}", e);
+            format!("Invalid WebSocket URL: {}", e)
+        })?;
 
-                    // 초기 연결 메시지 전송
-                    let connect_msg = serde_json::json!({
-                        "ver": "2",
-                        "cmd": 100,
-                        "svcid": "game",
-                        "cid": chat_channel_id,
-                        "bdy": {
-                            "uid": null,
-                            "devType": 2001,
-                            "accTkn": access_token,
-                            "auth": "READ"
-                        },
-                        "tid": 1
-                    });
+        match connect_async(url).await {
+            Ok((ws_stream, _)) => {
+                println!("Successfully connected to WebSocket!");
+                Ok(ws_stream)
+            }
+            Err(e) => {
+                println!("Failed to connect: {}", e);
+                Err(format!("Failed to connect to WebSocket: {}", e))
+            }
+        }
+    }
 
-                    println!("Sending connect message: {}", connect_msg);
-                    write.send(Message::Text(connect_msg.to_string())).await?;
-
-                    // 핑 메시지 전송을 위한 채널
-                    let (ping_tx, mut ping_rx) = mpsc::channel::<String>(10);
-                    // 종료 메시지 전송을 위한 채널
-                    let (close_tx, mut close_rx) = mpsc::channel::<()>(1);
-
-                    // 핑 메시지 전송을 위한 태스크
-                    let is_connected = self.is_connected.clone();
-                    let ping_handle = tokio::spawn(async move {
-                        let mut interval =
-                            tokio::time::interval(tokio::time::Duration::from_secs(20));
-                        loop {
-                            interval.tick().await;
-                            if !*is_connected.lock().await {
-                                break;
-                            }
-                            // PING 메시지 전송
-                            let ping_msg = serde_json::json!({
-                                "ver": "2",
-                                "cmd": 0,
-                                "tid": 2
-                            });
-                            if let Err(e) = ping_tx.send(ping_msg.to_string()).await {
-                                println!("Failed to queue ping: {}", e);
-                                break;
-                            }
-                            println!("Queued PING");
-                        }
-                    });
+    async fn connect_websocket(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let chat_channel_id = self
+            .chat_channel_id
+            .clone()
+            .ok_or("No chat channel ID available")?;
+        let access_token = self
+            .access_token
+            .clone()
+            .ok_or("No access token available")?;
 
-                    // 태스크 핸들 먼저 저장
-                    *self.ping_task.lock().await = Some(ping_handle);
-
-                    // 종료 채널을 disconnect에서 사용할 수 있도록 저장
-                    *self.close_tx.lock().await = Some(close_tx);
-
-                    // 메시지 수신 루프
-                    let app_handle = self.app_handle.clone();
-                    let is_connected_clone = self.is_connected.clone();
-
-                    let ws_handle = tokio::spawn(async move {
-                        loop {
-                            tokio::select! {
-                                // WebSocket 메시지 수신
-                                Some(msg) = read.next() => {
-                                    match msg {
-                                        Ok(Message::Text(text)) => {
-                                    println!("Received message: {}", text);
-                                    if let Ok(json_msg) =
-                                        serde_json::from_str::<serde_json::Value>(&text)
-                                    {
-                                        // 메시지 타입에 따라 처리
-                                        let cmd = json_msg["cmd"].as_i64().unwrap_or(0);
-                                        match cmd {
-                                            0 => {
-                                                // PONG 응답
-                                                println!("Received PONG");
-                                            }
-                                            93101 => {
-                                                // 채팅 메시지
-                                                println!("Processing chat message");
-                                                let body = if json_msg.get("bdy").is_some() {
-                                                    json_msg["bdy"].clone()
-                                                } else {
-                                                    continue;
-                                                };
-
-                                                if let Ok(messages) = serde_json::from_value::<
-                                                    Vec<serde_json::Value>,
-                                                >(body) {
-                                                    for msg in messages {
-                                                        // Parse profile JSON string
-                                                        if let (Some(profile_str), Some(msg_text), Some(msg_time)) = (
-                                                            msg.get("profile").and_then(|p| p.as_str()),
-                                                            msg.get("msg").and_then(|m| m.as_str()),
-                                                            msg.get("msgTime").and_then(|t| t.as_i64()),
-                                                        ) {
-                                                            if let Ok(profile) = serde_json::from_str::<crate::chzzk::types::ChatProfile>(profile_str) {
-                                                                // Create simplified chat message for frontend
-                                                                let chat_event = ChatMessage {
-                                                                    uid: msg.get("uid").and_then(|u| u.as_str()).unwrap_or("").to_string(),
-                                                                    msg_time,
-                                                                    profile: profile_str.to_string(),
-                                                                    msg: msg_text.to_string(),
-                                                                    msg_type_code: msg.get("msgTypeCode").and_then(|t| t.as_i64()).unwrap_or(0) as i32,
-                                                                    msg_status_type: msg.get("msgStatusType").and_then(|s| s.as_str()).unwrap_or("").to_string(),
-                                                                    extras: msg.get("extras").and_then(|e| e.as_str()).unwrap_or("{}").to_string(),
-                                                                    ctime: msg.get("ctime").and_then(|c| c.as_i64()).unwrap_or(0),
-                                                                    utime: msg.get("utime").and_then(|u| u.as_i64()).unwrap_or(0),
-                                                                    msg_tid: msg.get("msgTid").and_then(|t| t.as_str()).map(|s| s.to_string()),
-                                                                    svcid: msg.get("svcid").and_then(|s| s.as_str()).unwrap_or("").to_string(),
-                                                                    cid: msg.get("cid").and_then(|c| c.as_str()).unwrap_or("").to_string(),
-                                                                    mbr_cnt: msg.get("mbrCnt").and_then(|m| m.as_i64()).unwrap_or(0) as i32,
-                                                                };
-
-                                                                // Emit with parsed profile for easier frontend use
-                                                                let _ = app_handle.emit(
-                                                                    "chzzk-chat-event",
-                                                                    serde_json::json!({
-                                                                        "type": "chat",
-                                                                        "uid": chat_event.uid,
-                                                                        "nickname": profile.nickname,
-                                                                        "msg": chat_event.msg,
-                                                                        "msgTime": chat_event.msg_time,
-                                                                        "profile": profile,
-                                                                    }),
-                                                                );
-                                                                println!("Emitted chat from {}: {}", profile.nickname, msg_text);
-                                                            }
-                                                        }
-                                                    }
-                                                }
-                                            }
-                                            93102 => {
-                                                // 도네이션 메시지
-                                                let body = if json_msg.get("bdy").is_some() {
-                                                    json_msg["bdy"].clone()
-                                                } else if json_msg.get("body").is_some() {
-                                                    json_msg["body"].clone()
-                                                } else {
-                                                    continue;
-                                                };
-
-                                                if let Ok(messages) = serde_json::from_value::<
-                                                    Vec<serde_json::Value>,
-                                                >(
-                                                    body.clone()
-                                                ) {
-                                                    for msg in messages {
-                                                        if let Ok(donation_msg) =
-                                                            serde_json::from_value::<DonationMessage>(
-                                                                msg,
-                                                            )
-                                                        {
-                                                            let _ = app_handle.emit(
-                                                                "chzzk-chat-event",
-                                                                ChatEvent::Donation(donation_msg),
-                                                            );
-                                                        }
-                                                    }
-                                                } else if let Ok(donation_msg) =
-                                                    serde_json::from_value::<DonationMessage>(body)
-                                                {
-                                                    let _ = app_handle.emit(
-                                                        "chzzk-chat-event",
-                                                        ChatEvent::Donation(donation_msg),
-                                                    );
-                                                }
-                                            }
-                                            94101 | 94102 | 94103 => {
-                                                // 시스템 메시지
-                                                if let Ok(system_msg) =
-                                                    serde_json::from_value::<SystemMessage>(
-                                                        json_msg["bdy"][0].clone(),
-                                                    )
-                                                {
-                                                    let _ = app_handle.emit(
-                                                        "chzzk-chat-event",
-                                                        ChatEvent::SystemMessage(system_msg),
-                                                    );
-                                                }
-                                            }
-                                            10000 => {
-                                                // PONG 응답 (서버에서 보내는 핑 응답)
-                                                println!("Received PONG from server");
-                                            }
-                                            10100 => {
-                                                // 연결 성공 응답
-                                                println!("Connection confirmed by server (10100)");
-                                                if let Some(bdy) = json_msg.get("bdy") {
-                                                    println!("Connection response: {}", bdy);
-                                                }
-                                            }
-                                            _ => {
-                                                println!(
-                                                    "Unknown command: {} - Full message: {}",
-                                                    cmd, text
-                                                );
-                                            }
-                                        }
-                                    }
-                                }
-                                        Ok(Message::Close(_)) => {
-                                            *is_connected_clone.lock().await = false;
-                                            let _ = app_handle
-                                                .emit("chzzk-chat-event", ChatEvent::Disconnected);
-                                            break;
-                                        }
-                                        Err(e) => {
-                                            let _ = app_handle.emit(
-                                                "chzzk-chat-event",
-                                                ChatEvent::Error {
-                                                    message: format!("WebSocket error: {}", e),
-                                                },
-                                            );
-                                            *is_connected_clone.lock().await = false;
-                                            break;
-                                        }
-                                        _ => {}
-                                    }
-                                }
-                                // 핑 메시지 전송
-                                Some(ping_msg) = ping_rx.recv() => {
-                                    if let Err(e) = write.send(Message::Text(ping_msg)).await {
-                                        println!("Failed to send ping: {}", e);
-                                        *is_connected_clone.lock().await = false;
-                                        break;
-                                    }
-                                    println!("Sent PING");
+        let ws_stream = self.open_websocket(&chat_channel_id, &access_token).await?;
+
+        // 연결 성공 이벤트 발생 (중복 방지)
+        {
+            let mut is_connected = self.is_connected.lock().await;
+            if !*is_connected {
+                self.emit_event(ChatEvent::Connected);
+                *is_connected = true;
+                println!("Emitted connected event and set is_connected to true");
+            } else {
+                println!("Already connected, skipping connected event emission");
+            }
+        }
+
+        // 종료 채널을 disconnect에서 사용할 수 있도록 저장
+        let (close_tx, close_rx) = mpsc::channel::<()>(1);
+        *self.close_tx.lock().await = Some(close_tx);
+
+        // 전송 채널은 SEND 권한으로 접속할 때만 열어둡니다
+        let (send_tx, send_rx) = mpsc::unbounded_channel::<String>();
+        *self.send_tx.lock().await = self.user_id_hash.as_ref().map(|_| send_tx);
+
+        // 세션이 끊어지면 재연결까지 담당하는 감시 태스크
+        let chat = self.clone();
+        let ws_handle = tokio::spawn(async move {
+            chat.supervise(ws_stream, chat_channel_id, access_token, close_rx, send_rx)
+                .await;
+        });
+
+        // 태스크 핸들 저장
+        *self.ws_task.lock().await = Some(ws_handle);
+
+        Ok(())
+    }
+
+    async fn supervise(
+        self,
+        mut ws_stream: WsStream,
+        chat_channel_id: String,
+        mut access_token: String,
+        mut close_rx: mpsc::Receiver<()>,
+        mut send_rx: mpsc::UnboundedReceiver<String>,
+    ) {
+        loop {
+            match self
+                .run_session(
+                    ws_stream,
+                    &chat_channel_id,
+                    &access_token,
+                    &mut close_rx,
+                    &mut send_rx,
+                )
+                .await
+            {
+                SessionEnd::Closed => break,
+                SessionEnd::Lost(reason) => {
+                    println!("Chat connection lost: {}", reason);
+                    *self.is_connected.lock().await = false;
+                }
+            }
+
+            match self.reconnect(&chat_channel_id, &mut close_rx).await {
+                Some((stream, token)) => {
+                    ws_stream = stream;
+                    access_token = token;
+                }
+                None => break,
+            }
+        }
+    }
+
+    async fn reconnect(
+        &self,
+        chat_channel_id: &str,
+        close_rx: &mut mpsc::Receiver<()>,
+    ) -> Option<(WsStream, String)> {
+        let max_attempts = self.reconnect_policy.max_attempts;
+
+        for attempt in 1..=max_attempts {
+            let delay = self.reconnect_policy.delay_for(attempt);
+            println!(
+                "Reconnecting in {}ms (attempt {}/{})",
+                delay.as_millis(),
+                attempt,
+                max_attempts
+            );
+            self.emit_event(ChatEvent::Reconnecting {
+                attempt,
+                delay_ms: delay.as_millis() as u64,
+            });
+
+            tokio::select! {
+                _ = tokio::time::sleep(delay) => {}
+                // 재연결 대기 중 연결 해제 요청
+                _ = close_rx.recv() => {
+                    println!("Received close signal while reconnecting");
+                    return None;
+                }
+            }
+
+            // 이전 토큰은 만료되었을 수 있으므로 새로 발급받습니다
+            let token = match self
+                .get_chat_access_token(chat_channel_id)
+                .await
+                .map_err(|e| e.to_string())
+            {
+                Ok(token) => token.access_token,
+                Err(e) => {
+                    println!("Failed to refresh chat access token: {}", e);
+                    continue;
+                }
+            };
+
+            match self.open_websocket(chat_channel_id, &token).await {
+                Ok(ws_stream) => {
+                    *self.is_connected.lock().await = true;
+                    self.emit_event(ChatEvent::Reconnected);
+                    return Some((ws_stream, token));
+                }
+                Err(e) => {
+                    println!("Reconnect attempt {} failed: {}", attempt, e);
+                }
+            }
+        }
+
+        self.emit_event(ChatEvent::Error {
+            message: format!("Failed to reconnect after {} attempts", max_attempts),
+        });
+        self.emit_event(ChatEvent::Disconnected);
+        None
+    }
+
+    async fn run_session(
+        &self,
+        ws_stream: WsStream,
+        chat_channel_id: &str,
+        access_token: &str,
+        close_rx: &mut mpsc::Receiver<()>,
+        send_rx: &mut mpsc::UnboundedReceiver<String>,
+    ) -> SessionEnd {
+        let (mut write, mut read) = ws_stream.split();
+
+        // 초기 연결 메시지 전송
+        let connect_msg = serde_json::json!({
+            "ver": "2",
+            "cmd": 100,
+            "svcid": "game",
+            "cid": chat_channel_id,
+            "bdy": {
+                "uid": self.user_id_hash,
+                "devType": 2001,
+                "accTkn": access_token,
+                "auth": if self.user_id_hash.is_some() { "SEND" } else { "READ" }
+            },
+            "tid": 1
+        });
+
+        println!("Sending connect message: {}", connect_msg);
+        if let Err(e) = write.send(Message::Text(connect_msg.to_string())).await {
+            return SessionEnd::Lost(format!("Failed to send connect message: {}", e));
+        }
+
+        // 핑 메시지 전송 주기
+        let mut ping_interval = tokio::time::interval(PING_INTERVAL);
+        ping_interval.tick().await;
+
+        // 채팅 전송에 필요한 세션 ID (연결 응답에서 받음)
+        let mut session_id: Option<String> = None;
+        let mut pending_messages: Vec<String> = Vec::new();
+
+        loop {
+            tokio::select! {
+                // WebSocket 메시지 수신
+                msg = read.next() => {
+                    match msg {
+                        Some(Ok(Message::Text(text))) => {
+                            println!("Received message: {}", text);
+                            // 서버 핑(cmd 0)에는 퐁(cmd 10000)으로 응답해야 연결이 유지됩니다
+                            if is_server_ping(&text) {
+                                let pong_msg = serde_json::json!({ "ver": "2", "cmd": 10000 });
+                                if let Err(e) = write.send(Message::Text(pong_msg.to_string())).await {
+                                    return SessionEnd::Lost(format!("Failed to send pong: {}", e));
                                 }
-                                // 종료 신호 수신
-                                Some(_) = close_rx.recv() => {
-                                    println!("Received close signal");
-                                    if let Err(e) = write.send(Message::Close(None)).await {
-                                        println!("Failed to send close frame: {}", e);
+                                println!("Sent PONG");
+                                continue;
+                            }
+                            if let Some(sid) = parse_session_id(&text) {
+                                println!("Chat session ID: {}", sid);
+                                // 연결 전에 요청된 메시지 전송
+                                for message in pending_messages.drain(..) {
+                                    let frame = self.send_frame(chat_channel_id, &sid, &message);
+                                    if let Err(e) = write.send(Message::Text(frame.to_string())).await {
+                                        return SessionEnd::Lost(format!("Failed to send chat message: {}", e));
                                     }
-                                    *is_connected_clone.lock().await = false;
-                                    break;
                                 }
+                                session_id = Some(sid);
                             }
+                            self.handle_text_message(&text);
+                        }
+                        Some(Ok(Message::Close(_))) => {
+                            return SessionEnd::Lost("Server closed the connection".to_string());
                         }
+                        Some(Err(e)) => {
+                            return SessionEnd::Lost(format!("WebSocket error: {}", e));
+                        }
+                        Some(Ok(_)) => {}
+                        None => {
+                            return SessionEnd::Lost("WebSocket stream ended".to_string());
+                        }
+                    }
+                }
+                // 핑 메시지 전송
+                _ = ping_interval.tick() => {
+                    let ping_msg = serde_json::json!({
+                        "ver": "2",
+                        "cmd": 0,
+                        "tid": 2
                     });
+                    if let Err(e) = write.send(Message::Text(ping_msg.to_string())).await {
+                        return SessionEnd::Lost(format!("Failed to send ping: {}", e));
+                    }
+                    println!("Sent PING");
+                }
+                // 채팅 메시지 전송
+                Some(message) = send_rx.recv() => {
+                    let Some(sid) = &session_id else {
+                        pending_messages.push(message);
+                        continue;
+                    };
+                    let frame = self.send_frame(chat_channel_id, sid, &message);
+                    if let Err(e) = write.send(Message::Text(frame.to_string())).await {
+                        return SessionEnd::Lost(format!("Failed to send chat message: {}", e));
+                    }
+                    println!("Sent chat message: {}", message);
+                }
+                // 종료 신호 수신
+                _ = close_rx.recv() => {
+                    println!("Received close signal");
+                    if let Err(e) = write.send(Message::Close(None)).await {
+                        println!("Failed to send close frame: {}", e);
+                    }
+                    return SessionEnd::Closed;
+                }
+            }
+        }
+    }
+
+    // 채팅 전송 메시지 (cmd 3101)
+    fn send_frame(
+        &self,
+        chat_channel_id: &str,
+        session_id: &str,
+        message: &str,
+    ) -> serde_json::Value {
+        let extras = serde_json::json!({
+            "chatType": "STREAMING",
+            "osType": "PC",
+            "streamingChannelId": self.channel_id,
+            "emojis": {}
+        });
+
+        serde_json::json!({
+            "ver": "2",
+            "cmd": 3101,
+            "svcid": "game",
+            "cid": chat_channel_id,
+            "sid": session_id,
+            "retry": false,
+            "bdy": {
+                "msg": message,
+                "msgTypeCode": 1,
+                "extras": extras.to_string(),
+                "msgTime": chrono::Utc::now().timestamp_millis()
+            },
+            "tid": 3
+        })
+    }
+
+    pub async fn can_send(&self) -> bool {
+        self.send_tx.lock().await.is_some()
+    }
+
+    // 현재 채팅 세션으로 메시지 전송 (로그인 정보가 있어야 함)
+    pub async fn send_message(&self, message: &str) -> Result<(), String> {
+        let message = message.trim();
+        if message.is_empty() {
+            return Err("Message is empty".to_string());
+        }
+
+        let send_tx = self.send_tx.lock().await;
+        let send_tx = send_tx
+            .as_ref()
+            .ok_or("Chat is read-only (login cookies are not configured)")?;
+
+        send_tx
+            .send(message.to_string())
+            .map_err(|_| "Chat session is closed".to_string())
+    }
+
+    fn handle_text_message(&self, text: &str) {
+        let Ok(json_msg) = serde_json::from_str::<serde_json::Value>(text) else {
+            return;
+        };
+
+        // 메시지 타입에 따라 처리
+        let cmd = json_msg["cmd"].as_i64().unwrap_or(0);
+        match cmd {
+            93101 => {
+                // 채팅 메시지
+                println!("Processing chat message");
+                let Some(body) = json_msg.get("bdy").cloned() else {
+                    return;
+                };
 
-                    // 태스크 핸들 저장
-                    *self.ws_task.lock().await = Some(ws_handle);
+                if let Ok(messages) = serde_json::from_value::<Vec<serde_json::Value>>(body) {
+                    for msg in messages {
+                        // Parse profile JSON string
+                        if let (Some(profile_str), Some(msg_text), Some(msg_time)) = (
+                            msg.get("profile").and_then(|p| p.as_str()),
+                            msg.get("msg").and_then(|m| m.as_str()),
+                            msg.get("msgTime").and_then(|t| t.as_i64()),
+                        ) {
+                            if let Ok(profile) = serde_json::from_str::<
+                                crate::chzzk::types::ChatProfile,
+                            >(profile_str)
+                            {
+                                println!("Received chat from {}: {}", profile.nickname, msg_text);
 
-                    return Ok(());
+                                // Emit with parsed profile
+                                self.emit_event(ChatEvent::Chat(ParsedChatMessage {
+                                    uid: msg
+                                        .get("uid")
+                                        .and_then(|u| u.as_str())
+                                        .unwrap_or("")
+                                        .to_string(),
+                                    nickname: profile.nickname.clone(),
+                                    msg: msg_text.to_string(),
+                                    msg_time,
+                                    profile,
+                                }));
+                            }
+                        }
+                    }
                 }
-                Err(e) => {
-                    println!("Failed to connect: {}", e);
-                    return Err(format!("Failed to connect to WebSocket: {}", e).into());
+            }
+            93102 => {
+                // 도네이션 메시지
+                let body = if json_msg.get("bdy").is_some() {
+                    json_msg["bdy"].clone()
+                } else if json_msg.get("body").is_some() {
+                    json_msg["body"].clone()
+                } else {
+                    return;
+                };
+
+                if let Ok(messages) = serde_json::from_value::<Vec<serde_json::Value>>(body.clone())
+                {
+                    for msg in messages {
+                        if let Some(donation_msg) = parse_donation(msg) {
+                            self.emit_event(ChatEvent::Donation(donation_msg));
+                        }
+                    }
+                } else if let Some(donation_msg) = parse_donation(body) {
+                    self.emit_event(ChatEvent::Donation(donation_msg));
+                }
+            }
+            94101 | 94102 | 94103 => {
+                // 시스템 메시지
+                if let Ok(system_msg) =
+                    serde_json::from_value::<SystemMessage>(json_msg["bdy"][0].clone())
+                {
+                    self.emit_event(ChatEvent::SystemMessage(system_msg));
+                }
+            }
+            10000 => {
+                // PONG 응답 (서버에서 보내는 핑 응답)
+                println!("Received PONG from server");
+            }
+            10100 => {
+                // 연결 성공 응답
+                println!("Connection confirmed by server (10100)");
+                if let Some(bdy) = json_msg.get("bdy") {
+                    println!("Connection response: {}", bdy);
                 }
-            },
-            Err(e) => {
-                println!("Invalid URL: {}", e);
-                return Err(format!("Invalid WebSocket URL: {}", e).into());
+            }
+            _ => {
+                println!("Unknown command: {} - Full message: {}", cmd, text);
             }
         }
     }
 
+    // 이벤트는 백엔드 이벤트 루프에서 처리된 뒤 프론트엔드로 전달됩니다
+    fn emit_event(&self, event: ChatEvent) {
+        let _ = self.event_tx.send(event);
+    }
+
     pub async fn disconnect(&mut self) {
         println!("Disconnecting from WebSocket...");
 
+        // 방송 대기 태스크 종료
+        if let Some(task) = self.watch_task.lock().await.take() {
+            task.abort();
+            println!("Watch task aborted");
+        }
+
+        self.close_session().await;
+
+        // 연결 해제 이벤트 발생
+        self.emit_event(ChatEvent::Disconnected);
+
+        println!("Disconnected from chat");
+    }
+
+    // 현재 WebSocket 세션만 종료 (방송 대기는 유지)
+    async fn close_session(&self) {
         // 연결 상태를 false로 설정
         *self.is_connected.lock().await = false;
 
+        // 전송 채널 닫기
+        self.send_tx.lock().await.take();
+
         // WebSocket에 종료 신호 전송
         if let Some(close_tx) = self.close_tx.lock().await.take() {
             let _ = close_tx.send(()).await;
@@ -471,18 +855,211 @@ impl ChzzkChat {
             task.abort();
             println!("WebSocket task aborted");
         }
+    }
+}
 
-        // 핑 태스크 종료
-        if let Some(task) = self.ping_task.lock().await.take() {
-            task.abort();
-            println!("Ping task aborted");
+fn is_server_ping(text: &str) -> bool {
+    serde_json::from_str::<serde_json::Value>(text)
+        .ok()
+        .and_then(|json_msg| json_msg["cmd"].as_i64())
+        == Some(0)
+}
+
+// 후원 메시지의 profile, extras는 JSON 문자열로 전달됩니다
+fn parse_donation(mut msg: serde_json::Value) -> Option<DonationMessage> {
+    for key in ["profile", "extras"] {
+        if let Some(raw) = msg.get(key).and_then(|v| v.as_str()).map(|s| s.to_string()) {
+            msg[key] = serde_json::from_str(&raw).unwrap_or(serde_json::Value::Null);
         }
+    }
 
-        // 연결 해제 이벤트 발생
-        let _ = self
-            .app_handle
-            .emit("chzzk-chat-event", ChatEvent::Disconnected);
+    let mut donation = serde_json::from_value::<DonationMessage>(msg).ok()?;
+    if donation.nickname.is_none() {
+        donation.nickname = donation.profile.as_ref().map(|p| p.nickname.clone());
+    }
+    donation.is_anonymous |= donation.extras.is_anonymous;
+    Some(donation)
+}
 
-        println!("Disconnected from chat");
+// 연결 응답(cmd 10100)에서 세션 ID 추출
+fn parse_session_id(text: &str) -> Option<String> {
+    let json_msg = serde_json::from_str::<serde_json::Value>(text).ok()?;
+    if json_msg["cmd"].as_i64()? != 10100 {
+        return None;
+    }
+    json_msg["bdy"]["sid"].as_str().map(|s| s.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chzzk::mock::{self, MockChzzkServer};
+
+    #[test]
+    fn test_reconnect_backoff() {
+        let policy = ReconnectPolicy::default();
+
+        assert_eq!(policy.delay_for(1), Duration::from_secs(1));
+        assert_eq!(policy.delay_for(2), Duration::from_secs(2));
+        assert_eq!(policy.delay_for(4), Duration::from_secs(8));
+        // 최대 대기 시간을 넘지 않습니다
+        assert_eq!(policy.delay_for(10), Duration::from_secs(60));
+        assert_eq!(policy.delay_for(40), Duration::from_secs(60));
+    }
+
+    #[test]
+    fn test_parse_session_id() {
+        let connected = r#"{"ver":"2","cmd":10100,"svcid":"game","cid":"N1abc","bdy":{"sid":"session-1","uuid":"u"},"retCode":0}"#;
+        assert_eq!(parse_session_id(connected).as_deref(), Some("session-1"));
+        assert_eq!(parse_session_id(r#"{"cmd":0}"#), None);
+    }
+
+    async fn next_event(event_rx: &mut mpsc::UnboundedReceiver<ChatEvent>) -> ChatEvent {
+        tokio::time::timeout(Duration::from_secs(5), event_rx.recv())
+            .await
+            .expect("Timed out waiting for chat event")
+            .expect("Event channel closed")
+    }
+
+    async fn connect_to_mock(
+        server: &MockChzzkServer,
+        auth: Option<ChzzkAuth>,
+    ) -> (ChzzkChat, mpsc::UnboundedReceiver<ChatEvent>) {
+        let (event_tx, mut event_rx) = mpsc::unbounded_channel();
+        let mut chat = ChzzkChat::new("mockchannel".to_string(), event_tx)
+            .with_endpoints(server.endpoints())
+            .with_auth(auth)
+            .with_reconnect_policy(ReconnectPolicy {
+                initial_delay: Duration::from_millis(10),
+                max_delay: Duration::from_millis(50),
+                max_attempts: 3,
+            });
+
+        chat.connect().await.map_err(|e| e.to_string()).unwrap();
+        assert!(matches!(
+            next_event(&mut event_rx).await,
+            ChatEvent::Connected
+        ));
+        (chat, event_rx)
+    }
+
+    #[tokio::test]
+    async fn test_connect_and_receive_chat() {
+        let mut server = MockChzzkServer::start().await;
+        let (_chat, mut event_rx) = connect_to_mock(&server, None).await;
+
+        let connect_msg = server.expect_cmd(100).await;
+        assert_eq!(connect_msg["cid"], mock::CHAT_CHANNEL_ID);
+        assert_eq!(connect_msg["bdy"]["accTkn"], "mock-token");
+        assert_eq!(connect_msg["bdy"]["auth"], "READ");
+
+        server.send(mock::chat_frame("user1", "시청자", "!sr 아이유"));
+        match next_event(&mut event_rx).await {
+            ChatEvent::Chat(chat) => {
+                assert_eq!(chat.uid, "user1");
+                assert_eq!(chat.nickname, "시청자");
+                assert_eq!(chat.msg, "!sr 아이유");
+            }
+            other => panic!("Expected chat event, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_receive_donation() {
+        let mut server = MockChzzkServer::start().await;
+        let (_chat, mut event_rx) = connect_to_mock(&server, None).await;
+        server.expect_cmd(100).await;
+
+        server.send(mock::donation_frame("user2", "후원자", 1000, "화이팅"));
+        match next_event(&mut event_rx).await {
+            ChatEvent::Donation(donation) => {
+                assert_eq!(donation.nickname.as_deref(), Some("후원자"));
+                assert_eq!(donation.msg.as_deref(), Some("화이팅"));
+                assert_eq!(donation.extras.pay_amount, 1000);
+            }
+            other => panic!("Expected donation event, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_server_ping() {
+        let mut server = MockChzzkServer::start().await;
+        let (_chat, _event_rx) = connect_to_mock(&server, None).await;
+        server.expect_cmd(100).await;
+
+        server.send(serde_json::json!({ "ver": "2", "cmd": 0 }));
+        let pong = server.expect_cmd(10000).await;
+        assert_eq!(pong["ver"], "2");
+    }
+
+    #[tokio::test]
+    async fn test_disconnect() {
+        let mut server = MockChzzkServer::start().await;
+        let (mut chat, mut event_rx) = connect_to_mock(&server, None).await;
+        server.expect_cmd(100).await;
+
+        chat.disconnect().await;
+        assert!(matches!(
+            next_event(&mut event_rx).await,
+            ChatEvent::Disconnected
+        ));
+        assert!(!chat.has_active_session().await);
+    }
+
+    #[tokio::test]
+    async fn test_reconnect_after_server_close() {
+        let mut server = MockChzzkServer::start().await;
+        let (_chat, mut event_rx) = connect_to_mock(&server, None).await;
+        server.expect_cmd(100).await;
+
+        server.drop_connection();
+        assert!(matches!(
+            next_event(&mut event_rx).await,
+            ChatEvent::Reconnecting { attempt: 1, .. }
+        ));
+        assert!(matches!(
+            next_event(&mut event_rx).await,
+            ChatEvent::Reconnected
+        ));
+
+        // 새 세션에서도 다시 연결 메시지를 보내고 채팅을 받습니다
+        server.expect_cmd(100).await;
+        assert_eq!(server.connections(), 2);
+
+        server.send(mock::chat_frame("user1", "시청자", "다시 왔어요"));
+        assert!(matches!(
+            next_event(&mut event_rx).await,
+            ChatEvent::Chat(_)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_send_message() {
+        let mut server = MockChzzkServer::start().await;
+        let auth = ChzzkAuth {
+            nid_aut: "aut".to_string(),
+            nid_ses: "ses".to_string(),
+        };
+        let (chat, _event_rx) = connect_to_mock(&server, Some(auth)).await;
+
+        let connect_msg = server.expect_cmd(100).await;
+        assert_eq!(connect_msg["bdy"]["auth"], "SEND");
+        assert_eq!(connect_msg["bdy"]["uid"], mock::USER_ID_HASH);
+
+        assert!(chat.can_send().await);
+        chat.send_message("안녕하세요").await.unwrap();
+
+        let frame = server.expect_cmd(3101).await;
+        assert_eq!(frame["sid"], mock::SESSION_ID);
+        assert_eq!(frame["bdy"]["msg"], "안녕하세요");
+    }
+
+    #[tokio::test]
+    async fn test_read_only_cannot_send() {
+        let server = MockChzzkServer::start().await;
+        let (chat, _event_rx) = connect_to_mock(&server, None).await;
+
+        assert!(!chat.can_send().await);
+        assert!(chat.send_message("안녕하세요").await.is_err());
     }
 }
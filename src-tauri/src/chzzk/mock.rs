@@ -0,0 +1,297 @@
+// 통합 테스트용 로컬 치지직 서버 (live-status, access-token, 채팅 WebSocket)
+use crate::chzzk::chat::ChzzkEndpoints;
+use futures_util::{SinkExt, StreamExt};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{mpsc, Mutex};
+use tokio_tungstenite::tungstenite::Message;
+
+pub const CHAT_CHANNEL_ID: &str = "N1mock";
+pub const SESSION_ID: &str = "mock-session";
+pub const USER_ID_HASH: &str = "mock-bot";
+
+const RECV_TIMEOUT: Duration = Duration::from_secs(5);
+
+// 현재 WebSocket 연결에 보낼 동작
+enum ServerAction {
+    Send(String),
+    Close,
+}
+
+pub struct MockChzzkServer {
+    http_addr: String,
+    ws_addr: String,
+    action_tx: mpsc::UnboundedSender<ServerAction>,
+    received_rx: mpsc::UnboundedReceiver<serde_json::Value>,
+    connections: Arc<AtomicUsize>,
+}
+
+impl MockChzzkServer {
+    pub async fn start() -> Self {
+        let http_listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let ws_listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let http_addr = http_listener.local_addr().unwrap().to_string();
+        let ws_addr = ws_listener.local_addr().unwrap().to_string();
+
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = http_listener.accept().await {
+                tokio::spawn(serve_http(stream));
+            }
+        });
+
+        let (action_tx, action_rx) = mpsc::unbounded_channel();
+        let (received_tx, received_rx) = mpsc::unbounded_channel();
+        let action_rx = Arc::new(Mutex::new(action_rx));
+        let connections = Arc::new(AtomicUsize::new(0));
+
+        let ws_connections = connections.clone();
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = ws_listener.accept().await {
+                ws_connections.fetch_add(1, Ordering::SeqCst);
+                tokio::spawn(serve_chat(stream, action_rx.clone(), received_tx.clone()));
+            }
+        });
+
+        Self {
+            http_addr,
+            ws_addr,
+            action_tx,
+            received_rx,
+            connections,
+        }
+    }
+
+    pub fn endpoints(&self) -> ChzzkEndpoints {
+        ChzzkEndpoints {
+            api_base: format!("http://{}", self.http_addr),
+            comm_api_base: format!("http://{}", self.http_addr),
+            chat_ws_url: Some(format!("ws://{}/chat", self.ws_addr)),
+        }
+    }
+
+    pub fn connections(&self) -> usize {
+        self.connections.load(Ordering::SeqCst)
+    }
+
+    pub fn send(&self, frame: serde_json::Value) {
+        self.action_tx
+            .send(ServerAction::Send(frame.to_string()))
+            .unwrap();
+    }
+
+    // 서버 쪽에서 현재 연결을 끊음 (재연결 테스트용)
+    pub fn drop_connection(&self) {
+        self.action_tx.send(ServerAction::Close).unwrap();
+    }
+
+    // 클라이언트가 보낸 프레임 중 cmd가 일치하는 첫 프레임
+    pub async fn expect_cmd(&mut self, cmd: i64) -> serde_json::Value {
+        loop {
+            let frame = tokio::time::timeout(RECV_TIMEOUT, self.received_rx.recv())
+                .await
+                .unwrap_or_else(|_| panic!("Timed out waiting for cmd {}", cmd))
+                .expect("Mock server stopped");
+            if frame["cmd"].as_i64() == Some(cmd) {
+                return frame;
+            }
+        }
+    }
+}
+
+async fn serve_http(mut stream: TcpStream) {
+    let mut buf = vec![0u8; 4096];
+    let mut request = Vec::new();
+    while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+        match stream.read(&mut buf).await {
+            Ok(0) | Err(_) => return,
+            Ok(n) => request.extend_from_slice(&buf[..n]),
+        }
+    }
+
+    let request = String::from_utf8_lossy(&request);
+    let path = request.split_whitespace().nth(1).unwrap_or("/");
+
+    let (status, body) = if path.contains("/live-status") {
+        (
+            "200 OK",
+            serde_json::json!({
+                "code": 200,
+                "content": {
+                    "liveTitle": "Mock stream",
+                    "status": "OPEN",
+                    "concurrentUserCount": 42,
+                    "accumulateCount": 100,
+                    "openDate": "2024-01-01 12:00:00",
+                    "closeDate": null,
+                    "chatChannelId": CHAT_CHANNEL_ID,
+                    "categoryType": null,
+                    "liveCategory": null,
+                    "liveCategoryValue": null
+                }
+            }),
+        )
+    } else if path.contains("/access-token") {
+        (
+            "200 OK",
+            serde_json::json!({
+                "code": 200,
+                "content": { "accessToken": "mock-token", "extraToken": "mock-extra" }
+            }),
+        )
+    } else if path.contains("/getUserStatus") {
+        // 쿠키가 있을 때만 로그인 상태로 응답
+        let logged_in = request.contains("NID_AUT=");
+        (
+            "200 OK",
+            serde_json::json!({
+                "code": 200,
+                "content": { "userIdHash": if logged_in { Some(USER_ID_HASH) } else { None } }
+            }),
+        )
+    } else {
+        ("404 Not Found", serde_json::json!({ "code": 404 }))
+    };
+
+    let body = body.to_string();
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    );
+    let _ = stream.write_all(response.as_bytes()).await;
+}
+
+async fn serve_chat(
+    stream: TcpStream,
+    action_rx: Arc<Mutex<mpsc::UnboundedReceiver<ServerAction>>>,
+    received_tx: mpsc::UnboundedSender<serde_json::Value>,
+) {
+    let Ok(ws_stream) = tokio_tungstenite::accept_async(stream).await else {
+        return;
+    };
+    let (mut write, mut read) = ws_stream.split();
+    let mut action_rx = action_rx.lock().await;
+
+    loop {
+        tokio::select! {
+            msg = read.next() => {
+                let Some(Ok(Message::Text(text))) = msg else {
+                    match msg {
+                        Some(Ok(_)) => continue,
+                        _ => return,
+                    }
+                };
+                let Ok(frame) = serde_json::from_str::<serde_json::Value>(&text) else {
+                    continue;
+                };
+
+                // 연결 요청에는 세션 ID, 핑에는 퐁으로 응답
+                let reply = match frame["cmd"].as_i64() {
+                    Some(100) => Some(serde_json::json!({
+                        "ver": "2",
+                        "cmd": 10100,
+                        "svcid": "game",
+                        "cid": CHAT_CHANNEL_ID,
+                        "bdy": { "sid": SESSION_ID, "uuid": frame["bdy"]["uid"] },
+                        "retCode": 0
+                    })),
+                    Some(0) => Some(serde_json::json!({ "ver": "2", "cmd": 10000 })),
+                    _ => None,
+                };
+                if let Some(reply) = reply {
+                    if write.send(Message::Text(reply.to_string())).await.is_err() {
+                        return;
+                    }
+                }
+
+                let _ = received_tx.send(frame);
+            }
+            action = action_rx.recv() => {
+                match action {
+                    Some(ServerAction::Send(text)) => {
+                        if write.send(Message::Text(text)).await.is_err() {
+                            return;
+                        }
+                    }
+                    Some(ServerAction::Close) | None => {
+                        let _ = write.send(Message::Close(None)).await;
+                        return;
+                    }
+                }
+            }
+        }
+    }
+}
+
+pub fn chat_frame(uid: &str, nickname: &str, msg: &str) -> serde_json::Value {
+    let profile = serde_json::json!({
+        "userIdHash": uid,
+        "nickname": nickname,
+        "profileImageUrl": null,
+        "userRoleCode": "common_user",
+        "badge": null,
+        "title": null,
+        "verifiedMark": false,
+        "activityBadges": [],
+        "streamingProperty": {},
+        "viewerBadges": []
+    });
+
+    serde_json::json!({
+        "ver": "2",
+        "cmd": 93101,
+        "svcid": "game",
+        "cid": CHAT_CHANNEL_ID,
+        "bdy": [{
+            "svcid": "game",
+            "cid": CHAT_CHANNEL_ID,
+            "uid": uid,
+            "profile": profile.to_string(),
+            "msg": msg,
+            "msgTypeCode": 1,
+            "msgStatusType": "NORMAL",
+            "extras": "{}",
+            "ctime": 1704078000000i64,
+            "utime": 1704078000000i64,
+            "msgTid": null,
+            "msgTime": 1704078000000i64
+        }]
+    })
+}
+
+pub fn donation_frame(uid: &str, nickname: &str, amount: i32, msg: &str) -> serde_json::Value {
+    let profile = serde_json::json!({
+        "userIdHash": uid,
+        "nickname": nickname,
+        "profileImageUrl": null,
+        "userRoleCode": "common_user",
+        "badge": null,
+        "streamingProperty": {}
+    });
+    let extras = serde_json::json!({
+        "isAnonymous": false,
+        "payType": "CURRENCY",
+        "payAmount": amount,
+        "donationType": "CHAT",
+        "donationImageUrl": null
+    });
+
+    serde_json::json!({
+        "ver": "2",
+        "cmd": 93102,
+        "svcid": "game",
+        "cid": CHAT_CHANNEL_ID,
+        "bdy": [{
+            "uid": uid,
+            "profile": profile.to_string(),
+            "msg": msg,
+            "msgTypeCode": 10,
+            "extras": extras.to_string(),
+            "msgTime": 1704078000000i64
+        }]
+    })
+}
@@ -1,4 +1,6 @@
 pub mod chat;
+#[cfg(test)]
+mod mock;
 pub mod types;
 
 pub use chat::ChzzkChat;
@@ -1,21 +1,40 @@
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Serialize, Serializer};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+// 프로필까지 파싱된 채팅 메시지 (역할, 구독 개월 수, 배지는 profile에서 계산)
+#[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct ChatMessage {
+pub struct ParsedChatMessage {
     pub uid: String,
-    pub msg_time: i64,
-    pub profile: String, // JSON string that needs to be parsed
+    pub nickname: String,
     pub msg: String,
-    pub msg_type_code: i32,
-    pub msg_status_type: String,
-    pub extras: String, // JSON string
-    pub ctime: i64,
-    pub utime: i64,
-    pub msg_tid: Option<String>,
-    pub svcid: String,
-    pub cid: String,
-    pub mbr_cnt: i32,
+    pub msg_time: i64,
+    pub profile: ChatProfile,
+}
+
+// 프론트엔드에서 바로 그릴 수 있도록 배지 목록을 함께 보냄
+impl Serialize for ParsedChatMessage {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        #[derive(Serialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Payload<'a> {
+            uid: &'a str,
+            nickname: &'a str,
+            msg: &'a str,
+            msg_time: i64,
+            badges: Vec<ChatBadge>,
+            profile: &'a ChatProfile,
+        }
+
+        Payload {
+            uid: &self.uid,
+            nickname: &self.nickname,
+            msg: &self.msg,
+            msg_time: self.msg_time,
+            badges: self.profile.badges(),
+            profile: &self.profile,
+        }
+        .serialize(serializer)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -24,13 +43,136 @@ pub struct ChatProfile {
     pub user_id_hash: String,
     pub nickname: String,
     pub profile_image_url: Option<String>,
-    pub user_role_code: String,
-    pub badge: Option<serde_json::Value>,
-    pub title: Option<String>,
+    pub user_role_code: UserRole,
+    pub badge: Option<Badge>,
+    pub title: Option<ProfileTitle>,
+    #[serde(default)]
     pub verified_mark: bool,
-    pub activity_badges: Vec<serde_json::Value>,
+    #[serde(default)]
+    pub activity_badges: Vec<ActivityBadge>,
     pub streaming_property: Option<StreamingProperty>,
-    pub viewer_badges: Vec<serde_json::Value>,
+    #[serde(default)]
+    pub viewer_badges: Vec<ViewerBadge>,
+}
+
+impl ChatProfile {
+    pub fn role(&self) -> UserRole {
+        self.user_role_code
+    }
+
+    pub fn is_streamer(&self) -> bool {
+        self.user_role_code == UserRole::Streamer
+    }
+
+    // 채팅 매니저 이상 (스트리머 포함)
+    pub fn is_manager(&self) -> bool {
+        self.user_role_code.at_least(UserRole::ChatManager)
+    }
+
+    pub fn is_following(&self) -> bool {
+        self.streaming_property
+            .as_ref()
+            .is_some_and(|p| p.following.is_some())
+    }
+
+    pub fn subscription(&self) -> Option<&SubscriptionInfo> {
+        self.streaming_property
+            .as_ref()
+            .and_then(|p| p.subscription.as_ref())
+    }
+
+    pub fn is_subscriber(&self) -> bool {
+        self.subscription().is_some()
+    }
+
+    // 누적 구독 개월 수 (구독하지 않았으면 0)
+    pub fn subscription_months(&self) -> u32 {
+        self.subscription()
+            .map(|s| s.accumulative_month.max(0) as u32)
+            .unwrap_or(0)
+    }
+
+    pub fn subscription_tier(&self) -> Option<u32> {
+        self.subscription().map(|s| s.tier.max(0) as u32)
+    }
+
+    // 화면에 표시할 배지 목록 (역할, 구독, 시청자, 활동 순)
+    pub fn badges(&self) -> Vec<ChatBadge> {
+        let mut badges = Vec::new();
+
+        if let Some(image_url) = self.badge.as_ref().and_then(|b| b.image_url.clone()) {
+            badges.push(ChatBadge {
+                kind: BadgeKind::Role,
+                image_url,
+            });
+        }
+
+        if let Some(badge) = self.subscription().and_then(|s| s.badge.as_ref()) {
+            badges.push(ChatBadge {
+                kind: BadgeKind::Subscription,
+                image_url: badge.image_url.clone(),
+            });
+        }
+
+        for viewer_badge in &self.viewer_badges {
+            if let Some(image_url) = viewer_badge.badge.image_url.clone() {
+                badges.push(ChatBadge {
+                    kind: BadgeKind::Viewer,
+                    image_url,
+                });
+            }
+        }
+
+        for activity_badge in self.activity_badges.iter().filter(|b| b.activated) {
+            if let Some(image_url) = activity_badge.image_url.clone() {
+                badges.push(ChatBadge {
+                    kind: BadgeKind::Activity,
+                    image_url,
+                });
+            }
+        }
+
+        badges
+    }
+}
+
+// 채널 내 사용자 역할
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UserRole {
+    #[default]
+    CommonUser,
+    #[serde(rename = "streaming_chat_manager")]
+    ChatManager,
+    #[serde(rename = "streaming_channel_manager", alias = "manager")]
+    Manager,
+    Streamer,
+    // 알 수 없는 역할 코드는 일반 사용자와 같은 권한으로 취급
+    #[serde(other)]
+    Unknown,
+}
+
+impl UserRole {
+    // 권한 수준 (높을수록 강한 권한)
+    pub fn level(&self) -> u8 {
+        match self {
+            UserRole::CommonUser | UserRole::Unknown => 0,
+            UserRole::ChatManager => 1,
+            UserRole::Manager => 2,
+            UserRole::Streamer => 3,
+        }
+    }
+
+    pub fn at_least(&self, other: UserRole) -> bool {
+        self.level() >= other.level()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileTitle {
+    pub name: String,
+    pub color: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -39,7 +181,7 @@ pub struct UserProfile {
     pub user_id_hash: String,
     pub nickname: String,
     pub profile_image_url: Option<String>,
-    pub user_role_code: String,
+    pub user_role_code: UserRole,
     pub badge: Option<Badge>,
     pub streaming_property: Option<StreamingProperty>,
 }
@@ -50,6 +192,56 @@ pub struct Badge {
     pub image_url: Option<String>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivityBadge {
+    pub badge_no: Option<i64>,
+    pub badge_id: Option<String>,
+    pub image_url: Option<String>,
+    #[serde(default)]
+    pub activated: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ViewerBadge {
+    pub badge: BadgeDetail,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BadgeDetail {
+    pub badge_no: Option<i64>,
+    pub badge_id: Option<String>,
+    pub image_url: Option<String>,
+    pub scope: Option<BadgeScope>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum BadgeScope {
+    Channel,
+    Global,
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum BadgeKind {
+    Role,
+    Subscription,
+    Viewer,
+    Activity,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChatBadge {
+    pub kind: BadgeKind,
+    pub image_url: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct StreamingProperty {
@@ -93,6 +285,7 @@ pub struct DonationMessage {
     pub nickname: Option<String>,
     pub profile: Option<UserProfile>,
     pub msg: Option<String>,
+    #[serde(default)]
     pub is_anonymous: bool,
     pub extras: DonationExtras,
 }
@@ -104,6 +297,8 @@ pub struct DonationExtras {
     pub pay_amount: i32,
     pub donation_type: String,
     pub donation_image_url: Option<String>,
+    #[serde(default)]
+    pub is_anonymous: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -152,11 +347,24 @@ pub struct ChatAccessToken {
     pub extra_token: String,
 }
 
+// 채팅 전송에 필요한 네이버 로그인 쿠키 (NID_AUT, NID_SES)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChzzkAuth {
+    pub nid_aut: String,
+    pub nid_ses: String,
+}
+
+impl ChzzkAuth {
+    pub fn cookie_header(&self) -> String {
+        format!("NID_AUT={}; NID_SES={}", self.nid_aut, self.nid_ses)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type")]
 pub enum ChatEvent {
     #[serde(rename = "chat")]
-    Chat(ChatMessage),
+    Chat(ParsedChatMessage),
     #[serde(rename = "donation")]
     Donation(DonationMessage),
     #[serde(rename = "systemMessage")]
@@ -165,6 +373,123 @@ pub enum ChatEvent {
     Connected,
     #[serde(rename = "disconnected")]
     Disconnected,
+    #[serde(rename = "reconnecting", rename_all = "camelCase")]
+    Reconnecting { attempt: u32, delay_ms: u64 },
+    #[serde(rename = "reconnected")]
+    Reconnected,
+    #[serde(rename = "streamStarted", rename_all = "camelCase")]
+    StreamStarted {
+        live_title: Option<String>,
+        open_date: Option<String>,
+    },
+    #[serde(rename = "streamEnded", rename_all = "camelCase")]
+    StreamEnded { close_date: Option<String> },
     #[serde(rename = "error")]
     Error { message: String },
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_common_user_profile() {
+        let profile: ChatProfile = serde_json::from_str(
+            r#"{
+                "userIdHash": "abc123",
+                "nickname": "viewer",
+                "profileImageUrl": "",
+                "userRoleCode": "common_user",
+                "badge": null,
+                "title": null,
+                "verifiedMark": false,
+                "activityBadges": [
+                    {"badgeNo": 1, "badgeId": "donation_newbie", "imageUrl": "https://example.com/a.png", "activated": true}
+                ],
+                "streamingProperty": {
+                    "subscription": {"accumulativeMonth": 7, "tier": 2, "badge": {"imageUrl": "https://example.com/sub.png"}},
+                    "following": {"followDate": "2024-01-01 12:00:00"}
+                },
+                "viewerBadges": [
+                    {"badge": {"badgeNo": 2, "badgeId": "fan", "imageUrl": "https://example.com/v.png", "scope": "CHANNEL"}}
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(profile.role(), UserRole::CommonUser);
+        assert!(!profile.is_manager());
+        assert!(profile.is_following());
+        assert!(profile.is_subscriber());
+        assert_eq!(profile.subscription_months(), 7);
+        assert_eq!(profile.subscription_tier(), Some(2));
+        assert_eq!(
+            profile.viewer_badges[0].badge.scope,
+            Some(BadgeScope::Channel)
+        );
+
+        let kinds: Vec<BadgeKind> = profile.badges().iter().map(|b| b.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                BadgeKind::Subscription,
+                BadgeKind::Viewer,
+                BadgeKind::Activity
+            ]
+        );
+    }
+
+    #[test]
+    fn test_manager_profile() {
+        let profile: ChatProfile = serde_json::from_str(
+            r##"{
+                "userIdHash": "def456",
+                "nickname": "manager",
+                "profileImageUrl": null,
+                "userRoleCode": "streaming_channel_manager",
+                "badge": {"imageUrl": "https://example.com/manager.png"},
+                "title": {"name": "매니저", "color": "#D9D9D9"},
+                "verifiedMark": false,
+                "activityBadges": [],
+                "streamingProperty": {},
+                "viewerBadges": []
+            }"##,
+        )
+        .unwrap();
+
+        assert_eq!(profile.role(), UserRole::Manager);
+        assert!(profile.is_manager());
+        assert!(!profile.is_streamer());
+        assert!(!profile.is_following());
+        assert_eq!(profile.subscription_months(), 0);
+        assert_eq!(profile.title.as_ref().unwrap().name, "매니저");
+        assert_eq!(profile.badges()[0].kind, BadgeKind::Role);
+
+        // 프론트엔드로 보낼 때 배지 목록을 프로필에서 계산해서 붙임
+        let chat = ParsedChatMessage {
+            uid: "def456".to_string(),
+            nickname: "manager".to_string(),
+            msg: "hi".to_string(),
+            msg_time: 0,
+            profile,
+        };
+        let json = serde_json::to_value(&chat).unwrap();
+        assert_eq!(json["badges"][0]["kind"], serde_json::json!("role"));
+        let parsed: ParsedChatMessage = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed.profile.role(), UserRole::Manager);
+    }
+
+    #[test]
+    fn test_user_role_order() {
+        let role: UserRole = serde_json::from_str(r#""streaming_chat_manager""#).unwrap();
+        assert_eq!(role, UserRole::ChatManager);
+
+        let role: UserRole = serde_json::from_str(r#""something_new""#).unwrap();
+        assert_eq!(role, UserRole::Unknown);
+        assert_eq!(role.level(), UserRole::CommonUser.level());
+
+        assert!(UserRole::Streamer.at_least(UserRole::Manager));
+        assert!(UserRole::Manager.at_least(UserRole::ChatManager));
+        assert!(!UserRole::ChatManager.at_least(UserRole::Manager));
+    }
+}
@@ -1,5 +1,8 @@
+use crate::chzzk::types::ChatProfile;
+use chrono::{DateTime, FixedOffset, NaiveDateTime, TimeZone, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{hash_map::Entry, HashMap};
+use std::time::{Duration, Instant};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CommandConfig {
@@ -7,11 +10,136 @@ pub struct CommandConfig {
     pub commands: HashMap<String, CommandDefinition>,
     #[serde(default)]
     pub playlist_limits: PlaylistLimits,
+    #[serde(default)]
+    pub custom_commands: HashMap<String, CustomCommand>,
+    #[serde(default)]
+    pub vote_skip: VoteSkipConfig,
+    #[serde(default)]
+    pub request_priority: RequestPriorityConfig,
+    #[serde(default)]
+    pub search_pick: SearchPickConfig,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+// 검색어 신청곡을 바로 추가하지 않고 검색 결과 중에서 고르게 하는 설정
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchPickConfig {
+    pub enabled: bool,
+    // 채팅으로 보여 줄 검색 결과 수
+    pub result_count: usize,
+    // 이 시간 안에 번호를 골라야 함 (초)
+    pub timeout_secs: u64,
+}
+
+impl Default for SearchPickConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            result_count: 3,
+            timeout_secs: 60,
+        }
+    }
+}
+
+// 후원/구독자 신청곡 우선 재생 설정
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RequestPriorityConfig {
+    // 후원 메시지에 담긴 신청곡을 우선 재생
+    pub donation_enabled: bool,
+    // 우선 재생에 필요한 최소 후원 금액 (치즈)
+    pub donation_min_amount: i32,
+    // 구독자 신청곡은 일반 신청곡보다 먼저 재생
+    pub subscriber_boost: bool,
+}
+
+impl Default for RequestPriorityConfig {
+    fn default() -> Self {
+        Self {
+            donation_enabled: true,
+            donation_min_amount: 1000,
+            subscriber_boost: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PlaylistLimits {
     pub user_limit: Option<usize>,
+    #[serde(default)]
+    pub max_duration_secs: Option<u64>,
+    #[serde(default)]
+    pub min_duration_secs: Option<u64>,
+    // 대기열에 있거나 duplicate_window_mins분 안에 재생된 영상은 거절
+    #[serde(default)]
+    pub prevent_duplicates: bool,
+    #[serde(default)]
+    pub duplicate_window_mins: u64,
+    #[serde(default)]
+    pub blocked_video_ids: Vec<String>,
+    #[serde(default)]
+    pub blocked_channels: Vec<String>,
+    // 제목에 포함되면 거절할 단어
+    #[serde(default)]
+    pub blocked_keywords: Vec<String>,
+    // YouTube 재생목록 링크로 한 번에 추가할 수 있는 최대 곡 수
+    #[serde(default = "default_max_playlist_import")]
+    pub max_playlist_import: usize,
+    // 방송 지역 국가 코드 (이 지역에서 볼 수 없는 영상은 거절)
+    #[serde(default = "default_region")]
+    pub region: String,
+}
+
+fn default_max_playlist_import() -> usize {
+    20
+}
+
+fn default_region() -> String {
+    "KR".to_string()
+}
+
+impl Default for PlaylistLimits {
+    fn default() -> Self {
+        Self {
+            user_limit: None,
+            max_duration_secs: None,
+            min_duration_secs: None,
+            prevent_duplicates: false,
+            duplicate_window_mins: 0,
+            blocked_video_ids: Vec::new(),
+            blocked_channels: Vec::new(),
+            blocked_keywords: Vec::new(),
+            max_playlist_import: default_max_playlist_import(),
+            region: default_region(),
+        }
+    }
+}
+
+// 건너뛰기 투표가 통과되는 기준
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum VoteSkipThreshold {
+    // 고정 표 수
+    Count { votes: usize },
+    // 최근 채팅 참여자 중 비율 (%)
+    Percent { percent: u8 },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VoteSkipConfig {
+    pub threshold: VoteSkipThreshold,
+    // 이 시간 안에 채팅한 사람을 참여자로 계산 (초)
+    pub active_window_secs: u64,
+    // 비율 기준일 때도 최소한 필요한 표 수
+    pub min_votes: usize,
+}
+
+impl Default for VoteSkipConfig {
+    fn default() -> Self {
+        Self {
+            threshold: VoteSkipThreshold::Percent { percent: 30 },
+            active_window_secs: 300,
+            min_votes: 2,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -20,6 +148,138 @@ pub struct CommandDefinition {
     pub aliases: Vec<String>,
     pub description: String,
     pub enabled: bool,
+    #[serde(default)]
+    pub permission: CommandPermission,
+    #[serde(default)]
+    pub cooldown: CommandCooldown,
+}
+
+// 사용자가 정의한 텍스트 응답 명령어
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomCommand {
+    pub name: String,
+    #[serde(default)]
+    pub aliases: Vec<String>,
+    // {user}, {args}, {uptime}, {viewers}, {song}, {count} 변수 사용 가능
+    pub response: String,
+    pub enabled: bool,
+    #[serde(default)]
+    pub permission: CommandPermission,
+    #[serde(default)]
+    pub cooldown: CommandCooldown,
+    // 누적 사용 횟수
+    #[serde(default)]
+    pub count: u64,
+}
+
+// 응답 템플릿에 채워 넣을 값
+#[derive(Debug, Clone, Default)]
+pub struct TemplateContext {
+    pub user: String,
+    pub args: Option<String>,
+    pub uptime: Option<String>,
+    pub viewers: Option<i32>,
+    pub song: Option<String>,
+    pub count: u64,
+}
+
+impl CustomCommand {
+    // 방송 정보를 조회해야 하는 템플릿인지 확인
+    pub fn needs_live_status(&self) -> bool {
+        self.response.contains("{uptime}") || self.response.contains("{viewers}")
+    }
+
+    pub fn render(&self, context: &TemplateContext) -> String {
+        self.response
+            .replace("{user}", &context.user)
+            .replace("{args}", context.args.as_deref().unwrap_or(""))
+            .replace(
+                "{uptime}",
+                context.uptime.as_deref().unwrap_or("방송 중이 아님"),
+            )
+            .replace("{viewers}", &context.viewers.unwrap_or(0).to_string())
+            .replace(
+                "{song}",
+                context.song.as_deref().unwrap_or("재생 중인 곡 없음"),
+            )
+            .replace("{count}", &context.count.to_string())
+    }
+}
+
+// 방송 시작 시각(KST, "yyyy-MM-dd HH:mm:ss")으로부터 경과 시간
+pub fn format_uptime(open_date: &str, now: DateTime<Utc>) -> Option<String> {
+    let naive = NaiveDateTime::parse_from_str(open_date, "%Y-%m-%d %H:%M:%S").ok()?;
+    let kst = FixedOffset::east_opt(9 * 3600)?;
+    let opened = kst.from_local_datetime(&naive).single()?;
+
+    let elapsed = now.signed_duration_since(opened).num_minutes().max(0);
+    let (hours, minutes) = (elapsed / 60, elapsed % 60);
+
+    Some(if hours > 0 {
+        format!("{}시간 {}분", hours, minutes)
+    } else {
+        format!("{}분", minutes)
+    })
+}
+
+// 명령어 재사용 대기시간 (0이면 사용 안 함)
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct CommandCooldown {
+    pub global_secs: u64,
+    pub user_secs: u64,
+    // 이 권한 이상이면 대기시간 무시
+    pub bypass: Option<CommandPermission>,
+}
+
+// 명령어를 사용할 수 있는 최소 권한
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(tag = "level", rename_all = "snake_case")]
+pub enum CommandPermission {
+    #[default]
+    Everyone,
+    Follower,
+    Subscriber {
+        #[serde(default)]
+        min_months: u32,
+    },
+    Manager,
+    Streamer,
+}
+
+impl CommandPermission {
+    // 프로필이 없으면 앱에서 직접 입력한 메시지이므로 항상 허용
+    pub fn allows(&self, profile: Option<&ChatProfile>) -> bool {
+        let Some(profile) = profile else {
+            return true;
+        };
+
+        match self {
+            CommandPermission::Everyone => true,
+            CommandPermission::Follower => {
+                profile.is_following() || profile.is_subscriber() || profile.is_manager()
+            }
+            CommandPermission::Subscriber { min_months } => {
+                (profile.is_subscriber() && profile.subscription_months() >= *min_months)
+                    || profile.is_manager()
+            }
+            CommandPermission::Manager => profile.is_manager(),
+            CommandPermission::Streamer => profile.is_streamer(),
+        }
+    }
+}
+
+// 명령어 실행이 거부된 이유
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "reason", rename_all = "snake_case")]
+pub enum CommandRejection {
+    PermissionDenied {
+        command: String,
+        required: CommandPermission,
+    },
+    Cooldown {
+        command: String,
+        remaining_secs: u64,
+    },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -30,6 +290,16 @@ pub enum ParsedCommand {
     Pause,
     Play,
     Clear,
+    Mode { arg: Option<String> },
+    History,
+    LastSong,
+    VoteSkip,
+    WrongSong,
+    MyQueue,
+    Position,
+    Undo,
+    Pick { arg: Option<String> },
+    Custom { name: String, args: Option<String> },
     Unknown { command: String },
 }
 
@@ -51,6 +321,12 @@ impl Default for CommandConfig {
                 aliases: vec!["sr".to_string(), "신청곡".to_string()],
                 description: "Add a song to the playlist".to_string(),
                 enabled: true,
+                permission: CommandPermission::Everyone,
+                cooldown: CommandCooldown {
+                    global_secs: 0,
+                    user_secs: 10,
+                    bypass: Some(CommandPermission::Manager),
+                },
             },
         );
 
@@ -61,6 +337,8 @@ impl Default for CommandConfig {
                 aliases: vec!["next".to_string(), "다음".to_string()],
                 description: "Skip to the next song".to_string(),
                 enabled: true,
+                permission: CommandPermission::Manager,
+                cooldown: CommandCooldown::default(),
             },
         );
 
@@ -71,6 +349,8 @@ impl Default for CommandConfig {
                 aliases: vec!["prev".to_string(), "이전".to_string()],
                 description: "Go to the previous song".to_string(),
                 enabled: true,
+                permission: CommandPermission::Manager,
+                cooldown: CommandCooldown::default(),
             },
         );
 
@@ -81,6 +361,8 @@ impl Default for CommandConfig {
                 aliases: vec!["정지".to_string()],
                 description: "Pause the current song".to_string(),
                 enabled: true,
+                permission: CommandPermission::Manager,
+                cooldown: CommandCooldown::default(),
             },
         );
 
@@ -91,24 +373,223 @@ impl Default for CommandConfig {
                 aliases: vec!["clearplaylist".to_string(), "초기화".to_string()],
                 description: "Clear the playlist".to_string(),
                 enabled: true,
+                permission: CommandPermission::Manager,
+                cooldown: CommandCooldown::default(),
+            },
+        );
+
+        commands.insert(
+            "undo".to_string(),
+            CommandDefinition {
+                name: "undo".to_string(),
+                aliases: vec!["되돌리기".to_string()],
+                description: "Undo the last playlist change".to_string(),
+                enabled: true,
+                permission: CommandPermission::Manager,
+                cooldown: CommandCooldown::default(),
+            },
+        );
+
+        commands.insert(
+            "mode".to_string(),
+            CommandDefinition {
+                name: "mode".to_string(),
+                aliases: vec!["repeat".to_string(), "재생모드".to_string()],
+                description: "Change the playback mode (off, all, one, shuffle)".to_string(),
+                enabled: true,
+                permission: CommandPermission::Manager,
+                cooldown: CommandCooldown::default(),
+            },
+        );
+
+        commands.insert(
+            "history".to_string(),
+            CommandDefinition {
+                name: "history".to_string(),
+                aliases: vec!["최근곡".to_string()],
+                description: "Show recently played songs".to_string(),
+                enabled: true,
+                permission: CommandPermission::Everyone,
+                cooldown: CommandCooldown {
+                    global_secs: 10,
+                    user_secs: 0,
+                    bypass: Some(CommandPermission::Manager),
+                },
+            },
+        );
+
+        commands.insert(
+            "lastsong".to_string(),
+            CommandDefinition {
+                name: "lastsong".to_string(),
+                aliases: vec!["지난곡".to_string()],
+                description: "Show the previously played song".to_string(),
+                enabled: true,
+                permission: CommandPermission::Everyone,
+                cooldown: CommandCooldown {
+                    global_secs: 10,
+                    user_secs: 0,
+                    bypass: Some(CommandPermission::Manager),
+                },
+            },
+        );
+
+        commands.insert(
+            "voteskip".to_string(),
+            CommandDefinition {
+                name: "voteskip".to_string(),
+                aliases: vec!["vs".to_string(), "스킵투표".to_string()],
+                description: "Vote to skip the current song".to_string(),
+                enabled: true,
+                permission: CommandPermission::Everyone,
+                cooldown: CommandCooldown::default(),
+            },
+        );
+
+        commands.insert(
+            "wrongsong".to_string(),
+            CommandDefinition {
+                name: "wrongsong".to_string(),
+                aliases: vec!["신청취소".to_string()],
+                description: "Remove your most recent song request".to_string(),
+                enabled: true,
+                permission: CommandPermission::Everyone,
+                cooldown: CommandCooldown::default(),
+            },
+        );
+
+        commands.insert(
+            "myqueue".to_string(),
+            CommandDefinition {
+                name: "myqueue".to_string(),
+                aliases: vec!["내신청곡".to_string()],
+                description: "List your queued songs".to_string(),
+                enabled: true,
+                permission: CommandPermission::Everyone,
+                cooldown: CommandCooldown {
+                    global_secs: 0,
+                    user_secs: 10,
+                    bypass: Some(CommandPermission::Manager),
+                },
+            },
+        );
+
+        commands.insert(
+            "position".to_string(),
+            CommandDefinition {
+                name: "position".to_string(),
+                aliases: vec!["내순서".to_string()],
+                description: "Show how many songs are ahead of yours".to_string(),
+                enabled: true,
+                permission: CommandPermission::Everyone,
+                cooldown: CommandCooldown {
+                    global_secs: 0,
+                    user_secs: 10,
+                    bypass: Some(CommandPermission::Manager),
+                },
+            },
+        );
+
+        commands.insert(
+            "pick".to_string(),
+            CommandDefinition {
+                name: "pick".to_string(),
+                aliases: vec!["선택".to_string()],
+                description: "Choose one of your song search results".to_string(),
+                enabled: true,
+                permission: CommandPermission::Everyone,
+                cooldown: CommandCooldown::default(),
             },
         );
 
         Self {
             prefix: "!".to_string(),
             commands,
-            playlist_limits: PlaylistLimits { user_limit: None },
+            playlist_limits: PlaylistLimits::default(),
+            custom_commands: HashMap::new(),
+            vote_skip: VoteSkipConfig::default(),
+            request_priority: RequestPriorityConfig::default(),
+            search_pick: SearchPickConfig::default(),
+        }
+    }
+}
+
+impl CommandConfig {
+    // 새 버전에서 추가된 기본 명령어를 기존 설정에 채워 넣음
+    pub fn add_missing_commands(&mut self) -> bool {
+        let mut added = false;
+        for (name, definition) in CommandConfig::default().commands {
+            if let Entry::Vacant(entry) = self.commands.entry(name) {
+                entry.insert(definition);
+                added = true;
+            }
+        }
+        added
+    }
+
+    // 기본 명령어와 사용자 정의 명령어의 이름/별칭 중복 확인
+    pub fn validate_custom_command(
+        &self,
+        command: &CustomCommand,
+        original_name: Option<&str>,
+    ) -> Result<(), String> {
+        let name = command.name.trim();
+        if name.is_empty() || name.contains(char::is_whitespace) {
+            return Err("명령어 이름은 공백 없이 입력해야 합니다".to_string());
+        }
+        if command.response.trim().is_empty() {
+            return Err("응답 내용을 입력해야 합니다".to_string());
         }
+
+        let builtin_names = self
+            .commands
+            .values()
+            .flat_map(|c| std::iter::once(&c.name).chain(c.aliases.iter()));
+        let custom_names = self
+            .custom_commands
+            .values()
+            .filter(|c| Some(c.name.as_str()) != original_name)
+            .flat_map(|c| std::iter::once(&c.name).chain(c.aliases.iter()));
+        let taken: Vec<String> = builtin_names
+            .chain(custom_names)
+            .map(|n| n.to_lowercase())
+            .collect();
+
+        let requested = std::iter::once(&command.name).chain(command.aliases.iter());
+        for requested_name in requested {
+            let requested_name = requested_name.to_lowercase();
+            if taken.contains(&requested_name) {
+                return Err(format!("이미 사용 중인 명령어입니다: {}", requested_name));
+            }
+        }
+
+        Ok(())
     }
 }
 
+// 권한/대기시간 확인에 필요한 명령어 규칙
+#[derive(Debug, Clone)]
+struct CommandRule {
+    name: String,
+    permission: CommandPermission,
+    cooldown: CommandCooldown,
+}
+
 pub struct CommandParser {
     config: CommandConfig,
+    // 명령어별 전체 대기시간 종료 시각
+    global_cooldowns: HashMap<String, Instant>,
+    // (명령어, 사용자)별 대기시간 종료 시각
+    user_cooldowns: HashMap<(String, String), Instant>,
 }
 
 impl CommandParser {
     pub fn new(config: CommandConfig) -> Self {
-        Self { config }
+        Self {
+            config,
+            global_cooldowns: HashMap::new(),
+            user_cooldowns: HashMap::new(),
+        }
     }
 
     pub fn config(&self) -> &CommandConfig {
@@ -116,6 +597,90 @@ impl CommandParser {
     }
 
     pub fn parse(&self, message: &str) -> Option<ParsedCommand> {
+        self.match_command(message).map(|(command, _)| command)
+    }
+
+    // 명령어를 파싱하고 보낸 사람의 권한과 대기시간을 확인
+    pub fn authorize(
+        &mut self,
+        message: &str,
+        user_id: &str,
+        profile: Option<&ChatProfile>,
+    ) -> Option<Result<ParsedCommand, CommandRejection>> {
+        let (command, rule) = self.match_command(message)?;
+        let Some(rule) = rule else {
+            return Some(Ok(command));
+        };
+
+        if !rule.permission.allows(profile) {
+            return Some(Err(CommandRejection::PermissionDenied {
+                command: rule.name,
+                required: rule.permission,
+            }));
+        }
+
+        let bypass = profile.is_none()
+            || rule
+                .cooldown
+                .bypass
+                .is_some_and(|permission| permission.allows(profile));
+        if bypass {
+            return Some(Ok(command));
+        }
+
+        if let Err(rejection) = self.check_cooldown(&rule, user_id) {
+            return Some(Err(rejection));
+        }
+
+        Some(Ok(command))
+    }
+
+    // 대기시간 중이면 거부하고, 아니면 이번 사용을 기록
+    fn check_cooldown(
+        &mut self,
+        rule: &CommandRule,
+        user_id: &str,
+    ) -> Result<(), CommandRejection> {
+        let now = Instant::now();
+        let user_key = (rule.name.clone(), user_id.to_string());
+
+        let remaining = [
+            self.global_cooldowns.get(&rule.name),
+            self.user_cooldowns.get(&user_key),
+        ]
+        .into_iter()
+        .flatten()
+        .map(|until| until.saturating_duration_since(now))
+        .max()
+        .unwrap_or_default();
+
+        if !remaining.is_zero() {
+            return Err(CommandRejection::Cooldown {
+                command: rule.name.clone(),
+                remaining_secs: remaining.as_secs_f64().ceil() as u64,
+            });
+        }
+
+        // 만료된 항목 정리
+        self.global_cooldowns.retain(|_, until| *until > now);
+        self.user_cooldowns.retain(|_, until| *until > now);
+
+        let cooldown = &rule.cooldown;
+        if cooldown.global_secs > 0 {
+            self.global_cooldowns.insert(
+                rule.name.clone(),
+                now + Duration::from_secs(cooldown.global_secs),
+            );
+        }
+        if cooldown.user_secs > 0 {
+            self.user_cooldowns
+                .insert(user_key, now + Duration::from_secs(cooldown.user_secs));
+        }
+
+        Ok(())
+    }
+
+    fn match_command(&self, message: &str) -> Option<(ParsedCommand, Option<CommandRule>)> {
         // Check if message starts with command prefix
         if !message.starts_with(&self.config.prefix) {
             return None;
@@ -141,7 +706,7 @@ impl CommandParser {
 
             // Check command name and aliases
             if cmd_def.name == command_name || cmd_def.aliases.contains(&command_name) {
-                return match cmd_def.name.as_str() {
+                let command = match cmd_def.name.as_str() {
                     "playlist" => {
                         if let Some(query) = args {
                             Some(ParsedCommand::Playlist { query })
@@ -160,17 +725,62 @@ impl CommandParser {
                         }
                     }
                     "clear" => Some(ParsedCommand::Clear),
+                    "mode" => Some(ParsedCommand::Mode { arg: args }),
+                    "history" => Some(ParsedCommand::History),
+                    "lastsong" => Some(ParsedCommand::LastSong),
+                    "voteskip" => Some(ParsedCommand::VoteSkip),
+                    "wrongsong" => Some(ParsedCommand::WrongSong),
+                    "myqueue" => Some(ParsedCommand::MyQueue),
+                    "position" => Some(ParsedCommand::Position),
+                    "undo" => Some(ParsedCommand::Undo),
+                    "pick" => Some(ParsedCommand::Pick { arg: args }),
                     _ => Some(ParsedCommand::Unknown {
                         command: command_name.clone(),
                     }),
                 };
+                let rule = CommandRule {
+                    name: cmd_def.name.clone(),
+                    permission: cmd_def.permission,
+                    cooldown: cmd_def.cooldown.clone(),
+                };
+                return command.map(|command| (command, Some(rule)));
+            }
+        }
+
+        // 사용자 정의 명령어
+        for custom in self.config.custom_commands.values() {
+            if !custom.enabled {
+                continue;
+            }
+
+            if custom.name.to_lowercase() == command_name
+                || custom
+                    .aliases
+                    .iter()
+                    .any(|alias| alias.to_lowercase() == command_name)
+            {
+                let rule = CommandRule {
+                    name: custom.name.clone(),
+                    permission: custom.permission,
+                    cooldown: custom.cooldown.clone(),
+                };
+                return Some((
+                    ParsedCommand::Custom {
+                        name: custom.name.clone(),
+                        args,
+                    },
+                    Some(rule),
+                ));
             }
         }
 
         // No matching command found
-        Some(ParsedCommand::Unknown {
-            command: command_name,
-        })
+        Some((
+            ParsedCommand::Unknown {
+                command: command_name,
+            },
+            None,
+        ))
     }
 
     pub fn is_command(&self, message: &str) -> bool {
@@ -180,6 +790,13 @@ impl CommandParser {
     pub fn update_config(&mut self, config: CommandConfig) {
         self.config = config;
     }
+
+    // 사용 횟수를 올리고 갱신된 명령어를 반환
+    pub fn record_custom_use(&mut self, name: &str) -> Option<CustomCommand> {
+        let command = self.config.custom_commands.get_mut(name)?;
+        command.count += 1;
+        Some(command.clone())
+    }
 }
 
 #[cfg(test)]
@@ -223,4 +840,315 @@ mod tests {
         // Test non-command
         assert!(parser.parse("regular message").is_none());
     }
+
+    fn profile(role: &str, streaming_property: &str) -> ChatProfile {
+        serde_json::from_str(&format!(
+            r#"{{
+                "userIdHash": "hash",
+                "nickname": "viewer",
+                "profileImageUrl": null,
+                "userRoleCode": "{}",
+                "badge": null,
+                "title": null,
+                "verifiedMark": false,
+                "activityBadges": [],
+                "streamingProperty": {},
+                "viewerBadges": []
+            }}"#,
+            role, streaming_property
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_mode_command() {
+        let parser = CommandParser::new(CommandConfig::default());
+        match parser.parse("!mode shuffle") {
+            Some(ParsedCommand::Mode { arg }) => assert_eq!(arg.as_deref(), Some("shuffle")),
+            other => panic!("Expected mode command, got {:?}", other),
+        }
+        assert!(matches!(
+            parser.parse("!재생모드"),
+            Some(ParsedCommand::Mode { arg: None })
+        ));
+    }
+
+    #[test]
+    fn test_history_commands() {
+        let parser = CommandParser::new(CommandConfig::default());
+        assert!(matches!(
+            parser.parse("!history"),
+            Some(ParsedCommand::History)
+        ));
+        assert!(matches!(
+            parser.parse("!지난곡"),
+            Some(ParsedCommand::LastSong)
+        ));
+    }
+
+    #[test]
+    fn test_queue_commands() {
+        let parser = CommandParser::new(CommandConfig::default());
+        assert!(matches!(
+            parser.parse("!wrongsong"),
+            Some(ParsedCommand::WrongSong)
+        ));
+        assert!(matches!(
+            parser.parse("!내신청곡"),
+            Some(ParsedCommand::MyQueue)
+        ));
+        assert!(matches!(
+            parser.parse("!position"),
+            Some(ParsedCommand::Position)
+        ));
+    }
+
+    #[test]
+    fn test_pick_command() {
+        let parser = CommandParser::new(CommandConfig::default());
+        assert!(matches!(
+            parser.parse("!pick 2"),
+            Some(ParsedCommand::Pick { arg: Some(arg) }) if arg == "2"
+        ));
+        assert!(matches!(
+            parser.parse("!선택"),
+            Some(ParsedCommand::Pick { arg: None })
+        ));
+        assert!(!CommandConfig::default().search_pick.enabled);
+    }
+
+    #[test]
+    fn test_undo_command_requires_manager() {
+        let mut parser = CommandParser::new(CommandConfig::default());
+        assert!(matches!(
+            parser.parse("!되돌리기"),
+            Some(ParsedCommand::Undo)
+        ));
+        let viewer = profile("common_user", "{}");
+        let manager = profile("streaming_chat_manager", "{}");
+        assert!(matches!(
+            parser.authorize("!undo", "viewer", Some(&viewer)),
+            Some(Err(CommandRejection::PermissionDenied { .. }))
+        ));
+        assert!(matches!(
+            parser.authorize("!undo", "manager", Some(&manager)),
+            Some(Ok(ParsedCommand::Undo))
+        ));
+    }
+
+    #[test]
+    fn test_add_missing_commands() {
+        let mut config = CommandConfig::default();
+        config.commands.remove("mode");
+        config.commands.get_mut("skip").unwrap().enabled = false;
+
+        assert!(config.add_missing_commands());
+        assert!(config.commands.contains_key("mode"));
+        // 기존 설정은 그대로 유지
+        assert!(!config.commands["skip"].enabled);
+        assert!(!config.add_missing_commands());
+    }
+
+    #[test]
+    fn test_command_permission() {
+        let mut parser = CommandParser::new(CommandConfig::default());
+        let viewer = profile("common_user", "{}");
+        let manager = profile("streaming_chat_manager", "{}");
+
+        assert!(matches!(
+            parser.authorize("!sr 아이유", "viewer", Some(&viewer)),
+            Some(Ok(ParsedCommand::Playlist { .. }))
+        ));
+        assert!(matches!(
+            parser.authorize("!skip", "viewer", Some(&viewer)),
+            Some(Err(CommandRejection::PermissionDenied { .. }))
+        ));
+        assert!(matches!(
+            parser.authorize("!skip", "manager", Some(&manager)),
+            Some(Ok(ParsedCommand::Skip))
+        ));
+        // 앱에서 직접 입력한 명령어는 권한 확인을 하지 않음
+        assert!(matches!(
+            parser.authorize("!skip", "local", None),
+            Some(Ok(ParsedCommand::Skip))
+        ));
+    }
+
+    #[test]
+    fn test_subscriber_permission() {
+        let permission = CommandPermission::Subscriber { min_months: 3 };
+        let short = profile(
+            "common_user",
+            r#"{"subscription": {"accumulativeMonth": 2, "tier": 1}}"#,
+        );
+        let long = profile(
+            "common_user",
+            r#"{"subscription": {"accumulativeMonth": 5, "tier": 1}}"#,
+        );
+        let follower = profile(
+            "common_user",
+            r#"{"following": {"followDate": "2024-01-01 00:00:00"}}"#,
+        );
+
+        assert!(!permission.allows(Some(&short)));
+        assert!(permission.allows(Some(&long)));
+        assert!(!permission.allows(Some(&follower)));
+        assert!(CommandPermission::Follower.allows(Some(&follower)));
+        assert!(!CommandPermission::Streamer.allows(Some(&long)));
+    }
+
+    #[test]
+    fn test_command_cooldown() {
+        let mut config = CommandConfig::default();
+        let playlist = config.commands.get_mut("playlist").unwrap();
+        playlist.cooldown = CommandCooldown {
+            global_secs: 0,
+            user_secs: 30,
+            bypass: Some(CommandPermission::Manager),
+        };
+        let mut parser = CommandParser::new(config);
+        let viewer = profile("common_user", "{}");
+        let manager = profile("streaming_chat_manager", "{}");
+
+        assert!(matches!(
+            parser.authorize("!sr 노래", "a", Some(&viewer)),
+            Some(Ok(_))
+        ));
+        match parser.authorize("!sr 노래", "a", Some(&viewer)) {
+            Some(Err(CommandRejection::Cooldown {
+                command,
+                remaining_secs,
+            })) => {
+                assert_eq!(command, "playlist");
+                assert!(remaining_secs > 0 && remaining_secs <= 30);
+            }
+            other => panic!("Expected cooldown rejection, got {:?}", other),
+        }
+
+        // 다른 사용자는 영향 없음
+        assert!(matches!(
+            parser.authorize("!sr 노래", "b", Some(&viewer)),
+            Some(Ok(_))
+        ));
+
+        // 매니저는 대기시간 무시
+        for _ in 0..2 {
+            assert!(matches!(
+                parser.authorize("!sr 노래", "m", Some(&manager)),
+                Some(Ok(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_global_cooldown() {
+        let mut config = CommandConfig::default();
+        config.commands.get_mut("skip").unwrap().permission = CommandPermission::Everyone;
+        config.commands.get_mut("skip").unwrap().cooldown = CommandCooldown {
+            global_secs: 10,
+            user_secs: 0,
+            bypass: None,
+        };
+        let mut parser = CommandParser::new(config);
+        let viewer = profile("common_user", "{}");
+
+        assert!(matches!(
+            parser.authorize("!skip", "a", Some(&viewer)),
+            Some(Ok(ParsedCommand::Skip))
+        ));
+        assert!(matches!(
+            parser.authorize("!skip", "b", Some(&viewer)),
+            Some(Err(CommandRejection::Cooldown { .. }))
+        ));
+    }
+
+    fn custom_command(name: &str, response: &str) -> CustomCommand {
+        CustomCommand {
+            name: name.to_string(),
+            aliases: vec!["디코".to_string()],
+            response: response.to_string(),
+            enabled: true,
+            permission: CommandPermission::Everyone,
+            cooldown: CommandCooldown::default(),
+            count: 0,
+        }
+    }
+
+    #[test]
+    fn test_custom_command() {
+        let mut config = CommandConfig::default();
+        config.custom_commands.insert(
+            "discord".to_string(),
+            custom_command("discord", "{user}님, 디스코드: https://discord.gg/example"),
+        );
+        let parser = CommandParser::new(config);
+
+        match parser.parse("!디코 hello") {
+            Some(ParsedCommand::Custom { name, args }) => {
+                assert_eq!(name, "discord");
+                assert_eq!(args.as_deref(), Some("hello"));
+            }
+            other => panic!("Expected Custom command, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_custom_command_template() {
+        let command = custom_command(
+            "info",
+            "{user} {args} | {uptime} | {viewers}명 | {song} | {count}번째",
+        );
+        assert!(command.needs_live_status());
+
+        let rendered = command.render(&TemplateContext {
+            user: "시청자".to_string(),
+            args: Some("안녕".to_string()),
+            uptime: Some("1시간 5분".to_string()),
+            viewers: Some(42),
+            song: Some("좋은날".to_string()),
+            count: 3,
+        });
+        assert_eq!(rendered, "시청자 안녕 | 1시간 5분 | 42명 | 좋은날 | 3번째");
+
+        let rendered = command.render(&TemplateContext {
+            user: "시청자".to_string(),
+            ..Default::default()
+        });
+        assert_eq!(
+            rendered,
+            "시청자  | 방송 중이 아님 | 0명 | 재생 중인 곡 없음 | 0번째"
+        );
+    }
+
+    #[test]
+    fn test_format_uptime() {
+        // 2024-01-01 12:00:00 KST == 03:00:00 UTC
+        let now = Utc.with_ymd_and_hms(2024, 1, 1, 4, 23, 0).unwrap();
+        assert_eq!(
+            format_uptime("2024-01-01 12:00:00", now).as_deref(),
+            Some("1시간 23분")
+        );
+        assert_eq!(format_uptime("invalid", now), None);
+    }
+
+    #[test]
+    fn test_validate_custom_command() {
+        let mut config = CommandConfig::default();
+        config
+            .custom_commands
+            .insert("discord".to_string(), custom_command("discord", "link"));
+
+        // 기본 명령어 별칭과 중복
+        assert!(config
+            .validate_custom_command(&custom_command("sr", "x"), None)
+            .is_err());
+        // 다른 사용자 정의 명령어와 중복
+        assert!(config
+            .validate_custom_command(&custom_command("other", "x"), None)
+            .is_err());
+        // 자기 자신을 수정하는 경우는 허용
+        assert!(config
+            .validate_custom_command(&custom_command("discord", "x"), Some("discord"))
+            .is_ok());
+    }
 }
@@ -1,24 +1,66 @@
+use crate::chzzk::types::ChzzkAuth;
 use crate::commands::{CommandConfig, PlaylistLimits};
+use crate::playlist::fallback::FallbackPlaylist;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
 use tauri::{AppHandle, Manager};
+use tokio::sync::mpsc;
+
+// 마지막 사용 후 이 시간 동안 추가 사용이 없으면 사용 횟수를 저장
+const COUNT_SAVE_DEBOUNCE: Duration = Duration::from_millis(500);
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AppConfig {
     pub command_config: CommandConfig,
+    #[serde(default)]
+    pub chzzk_config: ChzzkConfig,
+    #[serde(default)]
+    pub fallback_playlist: FallbackPlaylist,
 }
 
 impl Default for AppConfig {
     fn default() -> Self {
         Self {
             command_config: CommandConfig::default(),
+            chzzk_config: ChzzkConfig::default(),
+            fallback_playlist: FallbackPlaylist::default(),
         }
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChzzkConfig {
+    // 방송 대기 모드에서 방송 상태를 확인하는 주기 (초)
+    pub watch_interval_secs: u64,
+    // 예전 버전에서 설정 파일에 저장하던 로그인 쿠키 (읽을 때 인증 파일로 옮기고 다시 쓰지 않음)
+    #[serde(default, rename = "auth", skip_serializing)]
+    legacy_auth: Option<ChzzkAuth>,
+    // 명령어 처리 결과를 채팅으로 답장
+    #[serde(default)]
+    pub reply_to_chat: bool,
+}
+
+impl Default for ChzzkConfig {
+    fn default() -> Self {
+        Self {
+            watch_interval_secs: 30,
+            legacy_auth: None,
+            reply_to_chat: false,
+        }
+    }
+}
+
+#[derive(Clone)]
 pub struct ConfigManager {
     config_path: PathBuf,
+    // 채팅 전송용 로그인 쿠키는 설정 파일과 따로 저장
+    auth_path: PathBuf,
+    // 파일을 읽고 고쳐 쓰는 사이에 다른 저장이 끼어들지 않도록 (복제본끼리 공유)
+    file_lock: Arc<Mutex<()>>,
 }
 
 impl ConfigManager {
@@ -35,15 +77,39 @@ impl ConfigManager {
         }
 
         let config_path = app_data_dir.join("config.json");
+        let auth_path = app_data_dir.join("chzzk_auth.json");
 
-        Ok(Self { config_path })
+        Ok(Self {
+            config_path,
+            auth_path,
+            file_lock: Arc::new(Mutex::new(())),
+        })
+    }
+
+    fn lock_file(&self) -> MutexGuard<'_, ()> {
+        self.file_lock
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
     }
 
     pub fn load(&self) -> Result<AppConfig, String> {
+        let _guard = self.lock_file();
+        self.read_file()
+    }
+
+    // 잠금을 잡은 채로 설정을 읽고 고쳐서 저장
+    fn update(&self, change: impl FnOnce(&mut AppConfig)) -> Result<(), String> {
+        let _guard = self.lock_file();
+        let mut config = self.read_file()?;
+        change(&mut config);
+        self.write_file(&config)
+    }
+
+    fn read_file(&self) -> Result<AppConfig, String> {
         if !self.config_path.exists() {
             // 설정 파일이 없으면 기본 설정을 생성하고 저장
             let default_config = AppConfig::default();
-            self.save(&default_config)?;
+            self.write_file(&default_config)?;
             return Ok(default_config);
         }
 
@@ -52,7 +118,13 @@ impl ConfigManager {
 
         // Try to parse with current structure
         match serde_json::from_str::<AppConfig>(&content) {
-            Ok(config) => Ok(config),
+            Ok(mut config) => {
+                let added_commands = config.command_config.add_missing_commands();
+                if self.move_legacy_auth(&mut config)? || added_commands {
+                    self.write_file(&config)?;
+                }
+                Ok(config)
+            }
             Err(_) => {
                 // Try to parse as legacy config (without playlist_limits)
                 match serde_json::from_str::<serde_json::Value>(&content) {
@@ -67,11 +139,12 @@ impl ConfigManager {
                         }
 
                         // Try to parse the modified JSON
-                        let config: AppConfig = serde_json::from_value(json)
+                        let mut config: AppConfig = serde_json::from_value(json)
                             .map_err(|e| format!("Failed to migrate config: {}", e))?;
+                        self.move_legacy_auth(&mut config)?;
 
                         // Save the migrated config
-                        self.save(&config)?;
+                        self.write_file(&config)?;
 
                         Ok(config)
                     }
@@ -81,25 +154,123 @@ impl ConfigManager {
         }
     }
 
-    pub fn save(&self, config: &AppConfig) -> Result<(), String> {
+    fn write_file(&self, config: &AppConfig) -> Result<(), String> {
         let content = serde_json::to_string_pretty(config)
             .map_err(|e| format!("Failed to serialize config: {}", e))?;
+        write_replacing(&self.config_path, &content)
+            .map_err(|e| format!("Failed to write config file: {}", e))
+    }
 
-        fs::write(&self.config_path, content)
-            .map_err(|e| format!("Failed to write config file: {}", e))?;
+    // 설정 파일에 남아 있던 로그인 쿠키를 인증 파일로 옮김 (옮겼으면 설정 파일을 다시 저장해야 함)
+    fn move_legacy_auth(&self, config: &mut AppConfig) -> Result<bool, String> {
+        let Some(auth) = config.chzzk_config.legacy_auth.take() else {
+            return Ok(false);
+        };
+        if !self.auth_path.exists() {
+            self.write_auth_file(&auth)?;
+        }
+        Ok(true)
+    }
 
-        Ok(())
+    fn write_auth_file(&self, auth: &ChzzkAuth) -> Result<(), String> {
+        let content = serde_json::to_string_pretty(auth)
+            .map_err(|e| format!("Failed to serialize chzzk auth: {}", e))?;
+        write_replacing(&self.auth_path, &content)
+            .map_err(|e| format!("Failed to write chzzk auth file: {}", e))
     }
 
     pub fn update_command_config(&self, command_config: CommandConfig) -> Result<(), String> {
-        let mut config = self.load()?;
-        config.command_config = command_config;
-        self.save(&config)?;
-        Ok(())
+        self.update(|config| config.command_config = command_config)
     }
 
     pub fn get_command_config(&self) -> Result<CommandConfig, String> {
         let config = self.load()?;
         Ok(config.command_config)
     }
+
+    pub fn update_chzzk_config(&self, chzzk_config: ChzzkConfig) -> Result<(), String> {
+        self.update(|config| config.chzzk_config = chzzk_config)
+    }
+
+    pub fn get_chzzk_config(&self) -> Result<ChzzkConfig, String> {
+        let config = self.load()?;
+        Ok(config.chzzk_config)
+    }
+
+    // 저장된 로그인 쿠키 (없으면 읽기 전용으로 연결)
+    pub fn get_chzzk_auth(&self) -> Result<Option<ChzzkAuth>, String> {
+        let _guard = self.lock_file();
+        // 예전 설정 파일의 쿠키가 먼저 옮겨지도록 설정부터 읽음
+        self.read_file()?;
+        if !self.auth_path.exists() {
+            return Ok(None);
+        }
+
+        let content = fs::read_to_string(&self.auth_path)
+            .map_err(|e| format!("Failed to read chzzk auth file: {}", e))?;
+        serde_json::from_str(&content)
+            .map(Some)
+            .map_err(|e| format!("Failed to parse chzzk auth file: {}", e))
+    }
+
+    // None이면 저장된 쿠키를 지움
+    pub fn update_chzzk_auth(&self, auth: Option<&ChzzkAuth>) -> Result<(), String> {
+        let _guard = self.lock_file();
+        match auth {
+            Some(auth) => self.write_auth_file(auth),
+            None if self.auth_path.exists() => fs::remove_file(&self.auth_path)
+                .map_err(|e| format!("Failed to remove chzzk auth file: {}", e)),
+            None => Ok(()),
+        }
+    }
+
+    pub fn update_fallback_playlist(&self, fallback: FallbackPlaylist) -> Result<(), String> {
+        self.update(|config| config.fallback_playlist = fallback)
+    }
+
+    pub fn get_fallback_playlist(&self) -> Result<FallbackPlaylist, String> {
+        let config = self.load()?;
+        Ok(config.fallback_playlist)
+    }
+
+    // 사용자 정의 명령어의 사용 횟수만 갱신 (그 사이 바뀐 다른 설정은 유지)
+    pub fn update_custom_command_counts(
+        &self,
+        counts: &HashMap<String, u64>,
+    ) -> Result<(), String> {
+        self.update(|config| {
+            for (name, count) in counts {
+                if let Some(command) = config.command_config.custom_commands.get_mut(name) {
+                    command.count = *count;
+                }
+            }
+        })
+    }
+}
+
+// 저장 도중 종료되어도 기존 파일이 깨지지 않도록 임시 파일에 먼저 기록
+fn write_replacing(path: &Path, content: &str) -> std::io::Result<()> {
+    let temp_path = path.with_extension("json.tmp");
+    fs::write(&temp_path, content)?;
+    fs::rename(&temp_path, path)
+}
+
+// 사용자 정의 명령어를 쓸 때마다 받은 사용 횟수를 모아서 한 번에 저장
+pub async fn run_command_count_saver(
+    config_manager: ConfigManager,
+    mut count_rx: mpsc::UnboundedReceiver<(String, u64)>,
+) {
+    while let Some((name, count)) = count_rx.recv().await {
+        let mut counts = HashMap::from([(name, count)]);
+        // 채널이 닫히거나 대기 시간이 지날 때까지 최신 횟수로 교체
+        while let Ok(Some((name, count))) =
+            tokio::time::timeout(COUNT_SAVE_DEBOUNCE, count_rx.recv()).await
+        {
+            counts.insert(name, count);
+        }
+
+        if let Err(e) = config_manager.update_custom_command_counts(&counts) {
+            println!("[Config] Failed to save custom command counts: {}", e);
+        }
+    }
 }
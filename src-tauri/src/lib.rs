@@ -9,16 +9,31 @@ use ai_service::{
     AIConfig, AIProvider, AIService, ChatMessage, ContextAnalysis, ScriptRecommendation,
     TargetAudience,
 };
+use chzzk::types::{ChatEvent, ChatProfile, ChzzkAuth, DonationMessage, ParsedChatMessage};
 use chzzk::ChzzkChat;
-use commands::{CommandConfig, CommandParser, ParsedCommand};
-use config::ConfigManager;
-use playlist::{PlaylistItem, PlaylistState};
+use commands::{
+    CommandConfig, CommandParser, CommandRejection, CustomCommand, ParsedCommand, TemplateContext,
+};
+use config::{run_command_count_saver, ChzzkConfig, ConfigManager};
+use futures_util::StreamExt;
+use playlist::fallback::{FallbackPlayer, FallbackPlaylist};
+use playlist::link::YouTubeRef;
+use playlist::pick::SearchPicks;
+use playlist::store::{run_playlist_saver, PlaylistStore};
+use playlist::transfer::PlaylistFormat;
+use playlist::undo::{PlaylistChange, UndoHistory};
+use playlist::voteskip::{VoteOutcome, VoteSkipTracker};
+use playlist::{
+    ItemSource, PlaybackMode, PlayedItem, PlaylistItem, PlaylistState, RequestPriority,
+};
 use serde::{Deserialize, Serialize};
-use std::collections::{HashSet, VecDeque};
+use std::collections::VecDeque;
 use std::sync::Arc;
+use std::time::Duration;
 use tauri::{AppHandle, Emitter, Manager, State};
-use tokio::sync::RwLock;
-use youtube::YouTubeService;
+use tokio::sync::{mpsc, RwLock};
+use youtube::cache::{CacheStats, MetadataCache};
+use youtube::{YouTubeService, YouTubeVideo};
 
 // 상태 타입
 #[derive(Debug, Clone)]
@@ -26,6 +41,8 @@ pub enum ChzzkState {
     Disconnected,
     Connecting { channel_id: String },
     Connected { channel_id: String },
+    Reconnecting { channel_id: String, attempt: u32 },
+    Watching { channel_id: String },
     Error { message: String },
 }
 
@@ -51,27 +68,56 @@ pub enum ChzzkEvent {
     StartDisconnect,
     DisconnectSuccess,
     DisconnectError { message: String },
+    ConnectionLost { attempt: u32 },
+    ReconnectSuccess,
+    ReconnectFailed { message: String },
+    StartWatch { channel_id: String },
+    ResumeWatch,
 }
 
 // 애플리케이션 상태 컨테이너
 struct AppState {
     connection_state: ChzzkState,
     chat_instance: Option<ChzzkChat>,
+    chat_event_tx: mpsc::UnboundedSender<ChatEvent>,
     ai_config: Option<AIConfig>,
     ai_service: Option<AIService>,
     chat_buffer: VecDeque<ChatMessage>,
     target_audience: Option<TargetAudience>,
     last_context_analysis: Option<ContextAnalysis>,
     playlist: PlaylistState,
+    playlist_save_tx: mpsc::UnboundedSender<PlaylistState>,
+    vote_skip: VoteSkipTracker,
+    fallback: FallbackPlayer,
+    playlist_undo: UndoHistory,
+    // 고르기 모드에서 번호를 기다리는 검색 결과
+    search_picks: SearchPicks,
     command_parser: CommandParser,
-    youtube_service: YouTubeService,
-    processed_commands: HashSet<String>,
+    // 조회 중에는 상태 잠금을 잡지 않도록 복제해서 사용
+    youtube_service: Arc<YouTubeService>,
     display_messages: VecDeque<DisplayChatMessage>,
     config_manager: ConfigManager,
+    // 답장 여부 등은 채팅마다 확인하므로 파일 대신 메모리에 둠
+    chzzk_config: ChzzkConfig,
+    chzzk_auth: Option<ChzzkAuth>,
+    // 사용자 정의 명령어 사용 횟수 저장 요청 (이름, 횟수)
+    command_count_tx: mpsc::UnboundedSender<(String, u64)>,
 }
 
 type SharedAppState = Arc<RwLock<AppState>>;
 
+// 방송 상태 확인 최소 주기 (초)
+const MIN_WATCH_INTERVAL_SECS: u64 = 5;
+
+// !history 명령어로 보여줄 최근 재생 곡 수
+const CHAT_HISTORY_COUNT: usize = 3;
+
+// !myqueue 명령어로 보여줄 신청곡 수
+const CHAT_QUEUE_COUNT: usize = 5;
+
+// 마지막 조회 후 이 시간 동안 추가 변경이 없으면 YouTube 캐시를 저장
+const CACHE_SAVE_DEBOUNCE: Duration = Duration::from_secs(2);
+
 // 상태 전환 로직
 fn transition_state(current: ChzzkState, event: ChzzkEvent) -> ChzzkState {
     use ChzzkEvent::*;
@@ -92,6 +138,32 @@ fn transition_state(current: ChzzkState, event: ChzzkEvent) -> ChzzkState {
         (Connected { .. }, DisconnectError { message }) => Error {
             message: message.clone(),
         },
+        (
+            Connected { channel_id } | Reconnecting { channel_id, .. },
+            ConnectionLost { attempt },
+        ) => Reconnecting {
+            channel_id: channel_id.clone(),
+            attempt: *attempt,
+        },
+        (Reconnecting { channel_id, .. }, ReconnectSuccess) => Connected {
+            channel_id: channel_id.clone(),
+        },
+        (Reconnecting { .. }, ReconnectFailed { message }) => Error {
+            message: message.clone(),
+        },
+        (Reconnecting { .. }, StartDisconnect) => Disconnected,
+        (Reconnecting { .. }, DisconnectSuccess) => Disconnected,
+        (Disconnected | Error { .. }, StartWatch { channel_id }) => Watching {
+            channel_id: channel_id.clone(),
+        },
+        (Watching { .. }, ConnectionSuccess { channel_id }) => Connected {
+            channel_id: channel_id.clone(),
+        },
+        (Connected { channel_id } | Reconnecting { channel_id, .. }, ResumeWatch) => Watching {
+            channel_id: channel_id.clone(),
+        },
+        (Watching { .. }, StartDisconnect) => Disconnected,
+        (Watching { .. }, DisconnectSuccess) => Disconnected,
         (Error { .. }, StartConnect { channel_id }) => Connecting {
             channel_id: channel_id.clone(),
         },
@@ -112,13 +184,17 @@ fn can_connect(state: &ChzzkState) -> Result<(), String> {
     match state {
         ChzzkState::Connected { .. } => Err("Already connected".to_string()),
         ChzzkState::Connecting { .. } => Err("Connection in progress".to_string()),
+        ChzzkState::Reconnecting { .. } => Err("Reconnection in progress".to_string()),
+        ChzzkState::Watching { .. } => Err("Already watching a channel".to_string()),
         _ => Ok(()),
     }
 }
 
 fn can_disconnect(state: &ChzzkState) -> Result<(), String> {
     match state {
-        ChzzkState::Connected { .. } => Ok(()),
+        ChzzkState::Connected { .. }
+        | ChzzkState::Reconnecting { .. }
+        | ChzzkState::Watching { .. } => Ok(()),
         _ => Err("Not connected".to_string()),
     }
 }
@@ -136,9 +212,10 @@ fn validate_channel_id(channel_id: &str) -> Result<String, String> {
 // IO 작업을 격리한 함수들
 async fn create_and_connect_chat(
     channel_id: String,
-    app_handle: AppHandle,
+    event_tx: mpsc::UnboundedSender<ChatEvent>,
+    auth: Option<ChzzkAuth>,
 ) -> Result<ChzzkChat, String> {
-    let mut chat = ChzzkChat::new(channel_id.clone(), app_handle);
+    let mut chat = ChzzkChat::new(channel_id.clone(), event_tx).with_auth(auth);
     chat.connect()
         .await
         .map_err(|e| format!("Failed to connect: {}", e))?;
@@ -150,11 +227,94 @@ async fn disconnect_chat(chat: &mut ChzzkChat) -> Result<(), String> {
     Ok(())
 }
 
+// 채팅 태스크에서 올라오는 이벤트를 처리하고 프론트엔드로 전달
+async fn run_chat_event_loop(
+    state: SharedAppState,
+    app_handle: AppHandle,
+    mut event_rx: mpsc::UnboundedReceiver<ChatEvent>,
+) {
+    while let Some(event) = event_rx.recv().await {
+        let _ = app_handle.emit("chzzk-chat-event", &event);
+
+        match event {
+            ChatEvent::Chat(chat) => {
+                {
+                    let mut app_state = state.write().await;
+                    push_chat_buffer(&mut app_state, chat.nickname.clone(), chat.msg.clone());
+                    app_state.vote_skip.record_activity(&chat.uid);
+                }
+
+                // 명령어 처리는 네트워크 요청이 있을 수 있어 별도 태스크에서 실행
+                tauri::async_runtime::spawn(dispatch_chat_command(
+                    chat,
+                    state.clone(),
+                    app_handle.clone(),
+                ));
+            }
+            ChatEvent::Donation(donation) => {
+                tauri::async_runtime::spawn(dispatch_donation_request(
+                    donation.clone(),
+                    state.clone(),
+                    app_handle.clone(),
+                ));
+
+                // 후원 메시지도 AI 분석에 포함 (명령어는 제외)
+                if let Some(msg) = donation
+                    .msg
+                    .filter(|m| !m.is_empty() && !m.starts_with('!'))
+                {
+                    let username = donation
+                        .nickname
+                        .unwrap_or_else(|| "익명의 후원자".to_string());
+                    let mut app_state = state.write().await;
+                    push_chat_buffer(
+                        &mut app_state,
+                        username,
+                        format!("[후원 {}원] {}", donation.extras.pay_amount, msg),
+                    );
+                }
+            }
+            event => sync_connection_state(&state, event).await,
+        }
+    }
+}
+
+// 연결 관련 이벤트로 상태 머신을 동기화
+async fn sync_connection_state(state: &SharedAppState, event: ChatEvent) {
+    let mut app_state = state.write().await;
+    let is_watching = match app_state.chat_instance.as_ref() {
+        Some(chat) => chat.is_watching().await,
+        None => false,
+    };
+
+    let transition = match event {
+        ChatEvent::Connected => match &app_state.connection_state {
+            ChzzkState::Watching { channel_id } => ChzzkEvent::ConnectionSuccess {
+                channel_id: channel_id.clone(),
+            },
+            _ => return,
+        },
+        ChatEvent::Reconnecting { attempt, .. } => ChzzkEvent::ConnectionLost { attempt },
+        ChatEvent::Reconnected => ChzzkEvent::ReconnectSuccess,
+        // 방송 대기 중이면 다음 상태 확인 때 다시 연결됩니다
+        ChatEvent::StreamEnded { .. } => ChzzkEvent::ResumeWatch,
+        ChatEvent::Error { .. } if is_watching => ChzzkEvent::ResumeWatch,
+        ChatEvent::Error { message } => ChzzkEvent::ReconnectFailed { message },
+        _ => return,
+    };
+
+    app_state.connection_state = transition_state(app_state.connection_state.clone(), transition);
+
+    // 재연결을 포기한 경우 인스턴스를 정리
+    if matches!(app_state.connection_state, ChzzkState::Error { .. }) {
+        app_state.chat_instance = None;
+    }
+}
+
 // 커맨드 핸들러들
 #[tauri::command]
 async fn connect_chzzk_chat(
     channel_id: String,
-    app_handle: AppHandle,
     state: State<'_, SharedAppState>,
 ) -> Result<String, String> {
     // 1. 채널 ID 검증
@@ -171,18 +331,20 @@ async fn connect_chzzk_chat(
     }
 
     // 3. 상태를 Connecting으로 전환
-    {
+    let (event_tx, auth) = {
         let mut app_state = state.write().await;
+        let auth = app_state.chzzk_auth.clone();
         app_state.connection_state = transition_state(
             app_state.connection_state.clone(),
             ChzzkEvent::StartConnect {
                 channel_id: validated_channel_id.clone(),
             },
         );
-    }
+        (app_state.chat_event_tx.clone(), auth)
+    };
 
     // 4. 실제 연결 수행 (IO 작업)
-    match create_and_connect_chat(validated_channel_id.clone(), app_handle).await {
+    match create_and_connect_chat(validated_channel_id.clone(), event_tx, auth).await {
         Ok(chat) => {
             // 성공: 상태 업데이트 및 인스턴스 저장
             let mut app_state = state.write().await;
@@ -213,6 +375,42 @@ async fn connect_chzzk_chat(
     }
 }
 
+#[tauri::command]
+async fn watch_chzzk_channel(
+    channel_id: String,
+    interval_secs: Option<u64>,
+    state: State<'_, SharedAppState>,
+) -> Result<String, String> {
+    let validated_channel_id = validate_channel_id(&channel_id)?;
+
+    // 연결 가능 여부 확인 후 Watching으로 전환
+    let (event_tx, interval, auth) = {
+        let mut app_state = state.write().await;
+        can_connect(&app_state.connection_state)?;
+
+        let interval_secs = interval_secs.unwrap_or(app_state.chzzk_config.watch_interval_secs);
+
+        app_state.connection_state = transition_state(
+            app_state.connection_state.clone(),
+            ChzzkEvent::StartWatch {
+                channel_id: validated_channel_id.clone(),
+            },
+        );
+        (
+            app_state.chat_event_tx.clone(),
+            Duration::from_secs(interval_secs.max(MIN_WATCH_INTERVAL_SECS)),
+            app_state.chzzk_auth.clone(),
+        )
+    };
+
+    let chat = ChzzkChat::new(validated_channel_id.clone(), event_tx).with_auth(auth);
+    chat.watch(interval).await;
+
+    state.write().await.chat_instance = Some(chat);
+
+    Ok(format!("Watching channel: {}", validated_channel_id))
+}
+
 #[tauri::command]
 async fn disconnect_chzzk_chat(state: State<'_, SharedAppState>) -> Result<String, String> {
     // 1. 연결 해제 가능 여부 확인
@@ -277,11 +475,55 @@ async fn get_chzzk_state(state: State<'_, SharedAppState>) -> Result<String, Str
         ChzzkState::Disconnected => "disconnected",
         ChzzkState::Connecting { .. } => "connecting",
         ChzzkState::Connected { .. } => "connected",
+        ChzzkState::Reconnecting { .. } => "reconnecting",
+        ChzzkState::Watching { .. } => "watching",
         ChzzkState::Error { .. } => "error",
     };
     Ok(state_name.to_string())
 }
 
+// 로그인 쿠키는 화면으로 보내지 않고 저장 여부만 알려줌
+#[derive(Debug, Clone, Serialize)]
+struct ChzzkSettings {
+    #[serde(flatten)]
+    config: ChzzkConfig,
+    auth_configured: bool,
+}
+
+#[tauri::command]
+async fn get_chzzk_config(state: State<'_, SharedAppState>) -> Result<ChzzkSettings, String> {
+    let app_state = state.read().await;
+    Ok(ChzzkSettings {
+        config: app_state.chzzk_config.clone(),
+        auth_configured: app_state.chzzk_auth.is_some(),
+    })
+}
+
+#[tauri::command]
+async fn update_chzzk_config(
+    config: ChzzkConfig,
+    state: State<'_, SharedAppState>,
+) -> Result<(), String> {
+    let mut app_state = state.write().await;
+    app_state
+        .config_manager
+        .update_chzzk_config(config.clone())?;
+    app_state.chzzk_config = config;
+    Ok(())
+}
+
+// 다음 연결부터 적용 (None이면 저장된 쿠키를 지움)
+#[tauri::command]
+async fn update_chzzk_auth(
+    auth: Option<ChzzkAuth>,
+    state: State<'_, SharedAppState>,
+) -> Result<(), String> {
+    let mut app_state = state.write().await;
+    app_state.config_manager.update_chzzk_auth(auth.as_ref())?;
+    app_state.chzzk_auth = auth;
+    Ok(())
+}
+
 // AI 설정 관련 커맨드
 #[tauri::command]
 async fn configure_ai(
@@ -332,256 +574,697 @@ async fn set_target_audience(
     Ok("Target audience configured".to_string())
 }
 
+// 앱에서 남기는 시스템 메시지 (플레이어 오류 등)는 AI 분석용 채팅 기록에만 추가
+// 채팅으로 들어온 것이 아니므로 명령어로 처리하지 않음
 #[tauri::command]
 async fn add_chat_message(
     username: String,
     message: String,
     state: State<'_, SharedAppState>,
-    app_handle: AppHandle,
 ) -> Result<(), String> {
     println!(
-        "[Backend] Received chat message: user={}, msg={}, time={}",
-        username,
-        message,
-        chrono::Utc::now().to_rfc3339()
-    );
-
-    // Create a unique command key for deduplication
-    // Use timestamp in seconds to have a wider time window for duplicate detection
-    let command_key = format!("{}-{}", username, message);
-
-    // Also create a time-based key for recent command tracking
-    let time_key = format!(
-        "{}-{}-{}",
-        username,
-        message,
-        chrono::Utc::now().timestamp() // Use seconds instead of milliseconds
+        "[Backend] Received system message: user={}, msg={}",
+        username, message
     );
 
-    // Check if message is a command
-    let is_command = {
-        let app_state = state.read().await;
-        app_state.command_parser.is_command(&message)
-    };
-
-    println!("[Backend] Is command: {}", is_command);
-
-    if is_command {
-        // Check if this command was already processed
-        {
-            let app_state = state.read().await;
-
-            // Check if exact same command was processed recently (within last 3 seconds)
-            let now = chrono::Utc::now().timestamp();
-            for processed_key in app_state.processed_commands.iter() {
-                if processed_key.starts_with(&format!("{}-", command_key)) {
-                    // Extract timestamp from the key
-                    if let Some(timestamp_str) = processed_key.split('-').last() {
-                        if let Ok(timestamp) = timestamp_str.parse::<i64>() {
-                            if now - timestamp <= 3 {
-                                println!(
-                                    "[Backend] Skipping duplicate command within 3s window: key={}, msg={}, time_diff={}s",
-                                    command_key, message, now - timestamp
-                                );
-                                return Ok(());
-                            }
-                        }
-                    }
-                }
-            }
-        }
-
-        // Process command
-        let parsed_command = {
-            let app_state = state.read().await;
-            app_state.command_parser.parse(&message)
-        };
-
-        println!("[Backend] Parsed command: {:?}", parsed_command);
-
-        if let Some(command) = parsed_command {
-            // Mark command as processed before executing
-            {
-                let mut app_state = state.write().await;
-                app_state.processed_commands.insert(time_key.clone());
-
-                // Clean up old commands (remove commands older than 60 seconds)
-                let now = chrono::Utc::now().timestamp();
-                let to_remove: Vec<String> = app_state
-                    .processed_commands
-                    .iter()
-                    .filter(|key| {
-                        // Extract timestamp from the key
-                        if let Some(timestamp_str) = key.split('-').last() {
-                            if let Ok(timestamp) = timestamp_str.parse::<i64>() {
-                                return now - timestamp > 60;
-                            }
-                        }
-                        true // Remove if can't parse timestamp
-                    })
-                    .cloned()
-                    .collect();
-                for key in to_remove {
-                    app_state.processed_commands.remove(&key);
-                }
-            }
-            match command {
-                ParsedCommand::Playlist { query } => {
-                    println!(
-                        "[Backend] Processing playlist command: query={}, user={}",
-                        query, username
-                    );
-                    // Handle playlist command
-                    process_playlist_command(
-                        query,
-                        username.clone(),
-                        state.inner().clone(),
-                        app_handle.clone(),
-                    )
-                    .await?;
-                    println!("[Backend] Playlist command processed successfully");
-                }
-                ParsedCommand::Skip => {
-                    skip_to_next(state.inner().clone(), app_handle.clone()).await?;
-                }
-                ParsedCommand::Previous => {
-                    go_to_previous(state.inner().clone(), app_handle.clone()).await?;
-                }
-                ParsedCommand::Pause => {
-                    pause_playback(state.inner().clone(), app_handle.clone()).await?;
-                }
-                ParsedCommand::Play => {
-                    resume_playback(state.inner().clone(), app_handle.clone()).await?;
-                }
-                ParsedCommand::Clear => {
-                    clear_playlist(state.inner().clone(), app_handle.clone()).await?;
-                }
-                ParsedCommand::Unknown { .. } => {
-                    // Ignore unknown commands
-                }
-            }
-        }
-    }
+    let mut app_state = state.write().await;
+    push_chat_buffer(&mut app_state, username, message);
+    Ok(())
+}
 
+// AI 분석용 채팅 버퍼에 메시지 추가
+fn push_chat_buffer(app_state: &mut AppState, username: String, message: String) {
     let chat_message = ChatMessage {
         username,
         message,
         timestamp: chrono::Utc::now().timestamp(),
     };
 
-    let mut app_state = state.write().await;
-
     // 버퍼가 가득 차면 오래된 메시지 제거
     if app_state.chat_buffer.len() >= 100 {
         app_state.chat_buffer.pop_front();
     }
 
     app_state.chat_buffer.push_back(chat_message);
-    Ok(())
 }
 
-#[tauri::command]
-async fn analyze_chat_context(state: State<'_, SharedAppState>) -> Result<ContextAnalysis, String> {
-    // Get messages with read lock
-    let messages: Vec<ChatMessage> = {
-        let app_state = state.read().await;
-        if app_state.ai_service.is_none() {
-            return Err("AI service not configured".to_string());
+// 치지직 채팅으로 들어온 명령어 실행
+async fn dispatch_chat_command(
+    chat: ParsedChatMessage,
+    state: SharedAppState,
+    app_handle: AppHandle,
+) {
+    let authorized = {
+        let mut app_state = state.write().await;
+        app_state
+            .command_parser
+            .authorize(&chat.msg, &chat.uid, Some(&chat.profile))
+    };
+
+    let command = match authorized {
+        Some(Ok(command)) => command,
+        Some(Err(rejection)) => {
+            report_command_rejection(&app_handle, &chat.nickname, rejection);
+            return;
         }
-        app_state.chat_buffer.iter().cloned().collect()
+        None => return,
     };
 
-    if messages.is_empty() {
-        return Err("No chat messages to analyze".to_string());
+    println!(
+        "[Backend] Parsed command from {}: {:?}",
+        chat.nickname, command
+    );
+
+    let reply_on_error = matches!(
+        command,
+        ParsedCommand::Playlist { .. }
+            | ParsedCommand::Mode { .. }
+            | ParsedCommand::VoteSkip
+            | ParsedCommand::Undo
+            | ParsedCommand::Pick { .. }
+    );
+    let sender = CommandSender {
+        user_id: chat.uid.clone(),
+        nickname: chat.nickname.clone(),
+        profile: Some(chat.profile.clone()),
+    };
+    let reply = match execute_command(command, sender, state.clone(), app_handle).await {
+        Ok(reply) => reply,
+        Err(e) => {
+            println!("[Backend] Command failed: {}", e);
+            // 신청곡, 모드 변경, 투표 실패 사유는 보낸 사람에게 알려줌
+            reply_on_error.then(|| format!("{}님, {}", chat.nickname, e))
+        }
+    };
+
+    if let Some(reply) = reply {
+        reply_to_chat(&state, &reply).await;
     }
+}
 
-    // Use write lock to access mutable AI service
-    let mut app_state = state.write().await;
+// 후원 메시지에 담긴 신청곡(명령어 또는 YouTube 링크)을 우선순위로 추가
+async fn dispatch_donation_request(
+    donation: DonationMessage,
+    state: SharedAppState,
+    app_handle: AppHandle,
+) {
+    let Some(msg) = donation.msg else {
+        return;
+    };
 
-    let ai_service = app_state
-        .ai_service
-        .as_mut()
-        .ok_or("AI service not configured".to_string())?;
+    let (query, config) = {
+        let app_state = state.read().await;
+        let query = match app_state.command_parser.parse(&msg) {
+            Some(ParsedCommand::Playlist { query }) => Some(query),
+            _ => msg
+                .split_whitespace()
+                .find(|word| playlist::is_youtube_url(word))
+                .map(str::to_string),
+        };
+        (
+            query,
+            app_state.command_parser.config().request_priority.clone(),
+        )
+    };
 
-    let context_analysis = ai_service.analyze_context(messages).await?;
+    let Some(query) = query else {
+        return;
+    };
+    // 기준 금액 미만의 후원은 신청곡으로 처리하지 않음
+    let amount = donation.extras.pay_amount;
+    if !config.donation_enabled || amount < config.donation_min_amount {
+        return;
+    }
 
-    // Store the analysis result
-    app_state.last_context_analysis = Some(context_analysis.clone());
+    let nickname = donation
+        .nickname
+        .unwrap_or_else(|| "익명의 후원자".to_string());
+    let reply = match process_playlist_command(
+        query,
+        nickname.clone(),
+        // 익명 후원은 신청자를 알 수 없음
+        (!donation.is_anonymous).then_some(donation.uid),
+        RequestPriority::Donation { amount },
+        false,
+        state.clone(),
+        app_handle,
+    )
+    .await
+    {
+        Ok(items) => format!(
+            "{}님의 후원 신청곡 {}이(가) 우선 재생됩니다",
+            nickname,
+            describe_items(&items)
+        ),
+        Err(e) => {
+            println!("[Backend] Donation request failed: {}", e);
+            format!("{}님, {}", nickname, e)
+        }
+    };
 
-    Ok(context_analysis)
+    reply_to_chat(&state, &reply).await;
 }
 
-#[tauri::command]
-async fn get_script_recommendations(
-    state: State<'_, SharedAppState>,
-) -> Result<ScriptRecommendation, String> {
-    // Get context and audience with read lock
-    let (context, audience) = {
+// 설정에서 답장이 켜져 있고 전송 가능한 경우에만 채팅으로 답장
+async fn reply_to_chat(state: &SharedAppState, message: &str) {
+    let chat = {
         let app_state = state.read().await;
-
-        if app_state.ai_service.is_none() {
-            return Err("AI service not configured".to_string());
+        if !app_state.chzzk_config.reply_to_chat {
+            return;
         }
-
-        let context = app_state
-            .last_context_analysis
-            .as_ref()
-            .ok_or("No context analysis available. Please analyze chat first.".to_string())?
-            .clone();
-
-        let audience = app_state
-            .target_audience
-            .as_ref()
-            .ok_or("Target audience not configured".to_string())?
-            .clone();
-
-        (context, audience)
+        app_state.chat_instance.clone()
     };
 
-    // Use write lock to access mutable AI service
-    let mut app_state = state.write().await;
-
-    let ai_service = app_state
-        .ai_service
-        .as_mut()
-        .ok_or("AI service not configured".to_string())?;
-
-    let recommendations = ai_service
-        .generate_script_recommendations(&context, &audience)
-        .await?;
+    let Some(chat) = chat else {
+        return;
+    };
+    if !chat.can_send().await {
+        return;
+    }
 
-    Ok(recommendations)
+    if let Err(e) = chat.send_message(message).await {
+        println!("[Backend] Failed to reply to chat: {}", e);
+    }
 }
 
 #[tauri::command]
-async fn get_ai_status(state: State<'_, SharedAppState>) -> Result<serde_json::Value, String> {
-    let app_state = state.read().await;
-
-    let is_configured = app_state.ai_config.is_some();
-    let provider = app_state
-        .ai_config
-        .as_ref()
-        .map(|c| match c.provider {
-            AIProvider::ChatGPT => "chatgpt",
-            AIProvider::Claude => "claude",
-            AIProvider::Gemini => "gemini",
-        })
-        .unwrap_or("none");
-
-    let has_target_audience = app_state.target_audience.is_some();
-    let chat_buffer_size = app_state.chat_buffer.len();
+async fn send_chat_message(
+    message: String,
+    state: State<'_, SharedAppState>,
+) -> Result<(), String> {
+    let chat = {
+        let app_state = state.read().await;
+        app_state
+            .chat_instance
+            .clone()
+            .ok_or("Chat is not connected")?
+    };
 
-    Ok(serde_json::json!({
-        "configured": is_configured,
-        "provider": provider,
-        "has_target_audience": has_target_audience,
-        "chat_buffer_size": chat_buffer_size
-    }))
+    chat.send_message(&message).await
 }
 
-// 앱 셋업 함수
-fn setup(app: &mut tauri::App) -> Result<(), Box<dyn std::error::Error>> {
-    let app_handle = app.handle().clone();
+// 거부된 명령어를 UI에 알림
+fn report_command_rejection(app_handle: &AppHandle, username: &str, rejection: CommandRejection) {
+    println!(
+        "[Backend] Command rejected for {}: {:?}",
+        username, rejection
+    );
+
+    match rejection {
+        CommandRejection::PermissionDenied { command, required } => {
+            let _ = app_handle.emit(
+                "command:permission-denied",
+                serde_json::json!({
+                    "username": username,
+                    "command": command,
+                    "required": required,
+                }),
+            );
+        }
+        CommandRejection::Cooldown {
+            command,
+            remaining_secs,
+        } => {
+            let _ = app_handle.emit(
+                "command:cooldown",
+                serde_json::json!({
+                    "username": username,
+                    "command": command,
+                    "remainingSecs": remaining_secs,
+                }),
+            );
+        }
+    }
+}
+
+// 명령어를 보낸 사람 (앱에서 직접 입력한 경우 profile 없음)
+struct CommandSender {
+    user_id: String,
+    nickname: String,
+    profile: Option<ChatProfile>,
+}
+
+// 명령어를 실행하고 채팅으로 보낼 답장이 있으면 반환
+async fn execute_command(
+    command: ParsedCommand,
+    sender: CommandSender,
+    state: SharedAppState,
+    app_handle: AppHandle,
+) -> Result<Option<String>, String> {
+    let username = sender.nickname.clone();
+    match command {
+        ParsedCommand::Playlist { query } => {
+            println!(
+                "[Backend] Processing playlist command: query={}, user={}",
+                query, username
+            );
+            // 설정에 따라 구독자 신청곡은 일반 신청곡보다 먼저 재생
+            let priority = {
+                let app_state = state.read().await;
+                let subscriber_boost = app_state
+                    .command_parser
+                    .config()
+                    .request_priority
+                    .subscriber_boost;
+                let is_subscriber = sender
+                    .profile
+                    .as_ref()
+                    .is_some_and(|profile| profile.is_subscriber());
+                if subscriber_boost && is_subscriber {
+                    RequestPriority::Subscriber
+                } else {
+                    RequestPriority::Normal
+                }
+            };
+            // 고르기 모드에서는 채팅 검색어 신청에 검색 결과 목록으로 답함 (링크는 바로 추가)
+            let pick_enabled = state
+                .read()
+                .await
+                .command_parser
+                .config()
+                .search_pick
+                .enabled;
+            if pick_enabled
+                && sender.profile.is_some()
+                && playlist::link::parse_youtube_ref(&query).is_none()
+            {
+                let reply = start_search_pick(&query, &sender, priority, &state).await?;
+                emit_command_response(&app_handle, "pick", &username, &reply)?;
+                return Ok(Some(reply));
+            }
+            let items = process_playlist_command(
+                query,
+                username.clone(),
+                Some(sender.user_id.clone()),
+                priority,
+                false,
+                state,
+                app_handle,
+            )
+            .await?;
+            println!("[Backend] Playlist command processed successfully");
+            return Ok(Some(format!(
+                "{}님의 신청곡 {}이(가) 추가되었습니다",
+                username,
+                describe_items(&items)
+            )));
+        }
+        ParsedCommand::Skip => {
+            skip_to_next(state, app_handle).await?;
+        }
+        ParsedCommand::Previous => {
+            go_to_previous(state, app_handle).await?;
+        }
+        ParsedCommand::Pause => {
+            pause_playback(state, app_handle).await?;
+        }
+        ParsedCommand::Play => {
+            resume_playback(state, app_handle).await?;
+        }
+        ParsedCommand::Clear => {
+            clear_playlist(state, app_handle).await?;
+        }
+        ParsedCommand::Mode { arg } => {
+            let mode = match arg {
+                Some(arg) => {
+                    let mode = PlaybackMode::from_arg(&arg).ok_or_else(|| {
+                        "알 수 없는 재생 모드입니다 (off, all, one, shuffle)".to_string()
+                    })?;
+                    change_playback_mode(mode, state, app_handle).await?;
+                    mode
+                }
+                None => state.read().await.playlist.mode,
+            };
+            return Ok(Some(format!("현재 재생 모드: {}", mode.label())));
+        }
+        ParsedCommand::History => {
+            let app_state = state.read().await;
+            let recent = app_state.playlist.recent_history(CHAT_HISTORY_COUNT);
+            if recent.is_empty() {
+                return Ok(Some("아직 재생한 곡이 없습니다".to_string()));
+            }
+            let titles: Vec<String> = recent
+                .iter()
+                .enumerate()
+                .map(|(i, played)| format!("{}. {}", i + 1, played.item.title))
+                .collect();
+            return Ok(Some(format!("최근 재생: {}", titles.join(" / "))));
+        }
+        ParsedCommand::LastSong => {
+            let app_state = state.read().await;
+            let reply = match app_state.playlist.last_played() {
+                Some(played) => format!(
+                    "지난 곡: '{}' (신청: {})",
+                    played.item.title, played.item.added_by
+                ),
+                None => "아직 재생한 곡이 없습니다".to_string(),
+            };
+            return Ok(Some(reply));
+        }
+        ParsedCommand::VoteSkip => {
+            let reply = vote_skip(&sender, state, app_handle).await?;
+            return Ok(Some(reply));
+        }
+        ParsedCommand::WrongSong => {
+            let mut app_state = state.write().await;
+            let reply = match app_state.playlist.remove_last_request(&sender.user_id) {
+                Some((index, item)) => {
+                    app_state.playlist_undo.record(PlaylistChange::Removed {
+                        item: Box::new(item.clone()),
+                        index,
+                    });
+                    emit_playlist_updated(&app_state, &app_handle)?;
+                    format!(
+                        "{}님의 신청곡 '{}'이(가) 취소되었습니다",
+                        username, item.title
+                    )
+                }
+                None => format!("{}님, 취소할 신청곡이 없습니다", username),
+            };
+            emit_command_response(&app_handle, "wrongsong", &username, &reply)?;
+            return Ok(Some(reply));
+        }
+        ParsedCommand::MyQueue => {
+            let app_state = state.read().await;
+            let queue = app_state.playlist.user_queue(&sender.user_id);
+            let reply = if queue.is_empty() {
+                format!("{}님, 대기 중인 신청곡이 없습니다", username)
+            } else {
+                let titles: Vec<String> = queue
+                    .iter()
+                    .take(CHAT_QUEUE_COUNT)
+                    .map(|(position, item)| format!("{}. {}", position, item.title))
+                    .collect();
+                let rest = queue.len().saturating_sub(CHAT_QUEUE_COUNT);
+                let more = if rest > 0 {
+                    format!(" 외 {}곡", rest)
+                } else {
+                    String::new()
+                };
+                format!("{}님의 신청곡: {}{}", username, titles.join(" / "), more)
+            };
+            emit_command_response(&app_handle, "myqueue", &username, &reply)?;
+            return Ok(Some(reply));
+        }
+        ParsedCommand::Position => {
+            let app_state = state.read().await;
+            let reply = if app_state.playlist.mode == PlaybackMode::Shuffle {
+                "셔플 모드에서는 순서를 알 수 없습니다".to_string()
+            } else {
+                match app_state.playlist.user_queue(&sender.user_id).first() {
+                    Some((1, item)) => format!(
+                        "{}님의 신청곡 '{}'이(가) 다음 곡입니다",
+                        username, item.title
+                    ),
+                    Some((position, item)) => format!(
+                        "{}님의 신청곡 '{}' 앞에 {}곡이 남았습니다",
+                        username,
+                        item.title,
+                        position - 1
+                    ),
+                    None => format!("{}님, 대기 중인 신청곡이 없습니다", username),
+                }
+            };
+            emit_command_response(&app_handle, "position", &username, &reply)?;
+            return Ok(Some(reply));
+        }
+        ParsedCommand::Undo => {
+            let description = undo_playlist_change(false, state, app_handle).await?;
+            return Ok(Some(format!(
+                "플레이리스트 변경을 되돌렸습니다: {}",
+                description
+            )));
+        }
+        ParsedCommand::Pick { arg } => {
+            let choice = arg
+                .as_deref()
+                .and_then(|arg| arg.trim().parse::<usize>().ok())
+                .ok_or_else(|| "번호를 입력해 주세요 (예: !pick 2)".to_string())?;
+            let (mut video, priority) = state
+                .write()
+                .await
+                .search_picks
+                .pick(&sender.user_id, choice)?;
+            let youtube_service = state.read().await.youtube_service.clone();
+            resolve_metadata(&mut video, &youtube_service).await;
+
+            let item = requested_item(
+                video,
+                username.clone(),
+                Some(sender.user_id.clone()),
+                priority,
+            );
+            let item = add_requested_item(item, false, state.clone(), app_handle.clone()).await?;
+            finish_requested_items(
+                &mut *state.write().await,
+                std::slice::from_ref(&item),
+                &app_handle,
+            )?;
+            return Ok(Some(format!(
+                "{}님의 신청곡 '{}'이(가) 추가되었습니다",
+                username, item.title
+            )));
+        }
+        ParsedCommand::Custom { name, args } => {
+            let response = run_custom_command(name, args, username, state, app_handle).await?;
+            return Ok(Some(response));
+        }
+        ParsedCommand::Unknown { .. } => {
+            // Ignore unknown commands
+        }
+    }
+    Ok(None)
+}
+
+// 시청자 투표로 건너뛰기 (매니저 이상과 앱 사용자는 바로 건너뜀)
+async fn vote_skip(
+    sender: &CommandSender,
+    state: SharedAppState,
+    app_handle: AppHandle,
+) -> Result<String, String> {
+    let instant = sender
+        .profile
+        .as_ref()
+        .is_none_or(|profile| profile.is_manager());
+    if instant {
+        skip_to_next(state, app_handle).await?;
+        return Ok(format!("{}님이 곡을 건너뛰었습니다", sender.nickname));
+    }
+
+    let outcome = {
+        let mut app_state = state.write().await;
+        let current = app_state
+            .playlist
+            .current_item()
+            .cloned()
+            .ok_or_else(|| "재생 중인 곡이 없습니다".to_string())?;
+        let config = app_state.command_parser.config().vote_skip.clone();
+        app_state.vote_skip.vote(&current, &sender.user_id, &config)
+    };
+
+    let progress = match &outcome {
+        VoteOutcome::Counted(progress)
+        | VoteOutcome::AlreadyVoted(progress)
+        | VoteOutcome::Passed(progress) => progress.clone(),
+    };
+    let _ = app_handle.emit("voteskip:progress", &progress);
+
+    match outcome {
+        VoteOutcome::Passed(progress) => {
+            skip_to_next(state, app_handle).await?;
+            Ok(format!(
+                "투표로 '{}'을(를) 건너뛰었습니다 ({}/{})",
+                progress.title, progress.votes, progress.required
+            ))
+        }
+        VoteOutcome::Counted(progress) => Ok(format!(
+            "건너뛰기 투표 {}/{}",
+            progress.votes, progress.required
+        )),
+        VoteOutcome::AlreadyVoted(progress) => Err(format!(
+            "이미 투표했습니다 ({}/{})",
+            progress.votes, progress.required
+        )),
+    }
+}
+
+// 사용자 정의 명령어의 응답을 만들어 UI에 전달
+async fn run_custom_command(
+    name: String,
+    args: Option<String>,
+    username: String,
+    state: SharedAppState,
+    app_handle: AppHandle,
+) -> Result<String, String> {
+    let (command, chat, song) = {
+        let mut app_state = state.write().await;
+        let command = app_state
+            .command_parser
+            .record_custom_use(&name)
+            .ok_or_else(|| format!("Custom command not found: {}", name))?;
+
+        // 사용 횟수는 모아서 나중에 저장
+        let _ = app_state
+            .command_count_tx
+            .send((name.clone(), command.count));
+
+        let song = app_state
+            .playlist
+            .current_index
+            .and_then(|i| app_state.playlist.items.get(i))
+            .map(|item| item.title.clone());
+
+        (command, app_state.chat_instance.clone(), song)
+    };
+
+    let live_status = match chat {
+        Some(chat) if command.needs_live_status() => chat.get_live_status().await.ok(),
+        _ => None,
+    }
+    .filter(|status| status.status == "OPEN");
+
+    let context = TemplateContext {
+        user: username.clone(),
+        args,
+        uptime: live_status
+            .as_ref()
+            .and_then(|status| status.open_date.as_deref())
+            .and_then(|open_date| commands::format_uptime(open_date, chrono::Utc::now())),
+        viewers: live_status
+            .as_ref()
+            .map(|status| status.concurrent_user_count),
+        song,
+        count: command.count,
+    };
+    let response = command.render(&context);
+
+    println!("[Backend] Custom command {} -> {}", name, response);
+
+    emit_command_response(&app_handle, &name, &username, &response)?;
+    Ok(response)
+}
+
+// 명령어 응답을 UI 채팅창에 표시
+fn emit_command_response(
+    app_handle: &AppHandle,
+    command: &str,
+    username: &str,
+    response: &str,
+) -> Result<(), String> {
+    app_handle
+        .emit(
+            "command:response",
+            serde_json::json!({
+                "command": command,
+                "username": username,
+                "response": response,
+            }),
+        )
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn analyze_chat_context(state: State<'_, SharedAppState>) -> Result<ContextAnalysis, String> {
+    // Get messages with read lock
+    let messages: Vec<ChatMessage> = {
+        let app_state = state.read().await;
+        if app_state.ai_service.is_none() {
+            return Err("AI service not configured".to_string());
+        }
+        app_state.chat_buffer.iter().cloned().collect()
+    };
+
+    if messages.is_empty() {
+        return Err("No chat messages to analyze".to_string());
+    }
+
+    // Use write lock to access mutable AI service
+    let mut app_state = state.write().await;
+
+    let ai_service = app_state
+        .ai_service
+        .as_mut()
+        .ok_or("AI service not configured".to_string())?;
+
+    let context_analysis = ai_service.analyze_context(messages).await?;
+
+    // Store the analysis result
+    app_state.last_context_analysis = Some(context_analysis.clone());
+
+    Ok(context_analysis)
+}
+
+#[tauri::command]
+async fn get_script_recommendations(
+    state: State<'_, SharedAppState>,
+) -> Result<ScriptRecommendation, String> {
+    // Get context and audience with read lock
+    let (context, audience) = {
+        let app_state = state.read().await;
+
+        if app_state.ai_service.is_none() {
+            return Err("AI service not configured".to_string());
+        }
+
+        let context = app_state
+            .last_context_analysis
+            .as_ref()
+            .ok_or("No context analysis available. Please analyze chat first.".to_string())?
+            .clone();
+
+        let audience = app_state
+            .target_audience
+            .as_ref()
+            .ok_or("Target audience not configured".to_string())?
+            .clone();
+
+        (context, audience)
+    };
+
+    // Use write lock to access mutable AI service
+    let mut app_state = state.write().await;
+
+    let ai_service = app_state
+        .ai_service
+        .as_mut()
+        .ok_or("AI service not configured".to_string())?;
+
+    let recommendations = ai_service
+        .generate_script_recommendations(&context, &audience)
+        .await?;
+
+    Ok(recommendations)
+}
+
+#[tauri::command]
+async fn get_ai_status(state: State<'_, SharedAppState>) -> Result<serde_json::Value, String> {
+    let app_state = state.read().await;
+
+    let is_configured = app_state.ai_config.is_some();
+    let provider = app_state
+        .ai_config
+        .as_ref()
+        .map(|c| match c.provider {
+            AIProvider::ChatGPT => "chatgpt",
+            AIProvider::Claude => "claude",
+            AIProvider::Gemini => "gemini",
+        })
+        .unwrap_or("none");
+
+    let has_target_audience = app_state.target_audience.is_some();
+    let chat_buffer_size = app_state.chat_buffer.len();
+
+    Ok(serde_json::json!({
+        "configured": is_configured,
+        "provider": provider,
+        "has_target_audience": has_target_audience,
+        "chat_buffer_size": chat_buffer_size
+    }))
+}
+
+// 앱 셋업 함수
+fn setup(app: &mut tauri::App) -> Result<(), Box<dyn std::error::Error>> {
+    let app_handle = app.handle().clone();
 
     // ConfigManager 생성
     let config_manager = ConfigManager::new(&app_handle)
@@ -591,161 +1274,397 @@ fn setup(app: &mut tauri::App) -> Result<(), Box<dyn std::error::Error>> {
     let command_config = config_manager
         .get_command_config()
         .unwrap_or_else(|_| CommandConfig::default());
+    let fallback_playlist = config_manager.get_fallback_playlist().unwrap_or_default();
+    let chzzk_config = config_manager.get_chzzk_config().unwrap_or_default();
+    let chzzk_auth = config_manager.get_chzzk_auth().unwrap_or_else(|e| {
+        println!("[Config] Failed to load chzzk auth: {}", e);
+        None
+    });
+    let (command_count_tx, command_count_rx) = mpsc::unbounded_channel();
+    tauri::async_runtime::spawn(run_command_count_saver(
+        config_manager.clone(),
+        command_count_rx,
+    ));
+
+    // 이전 실행에서 저장된 플레이리스트 복원
+    let playlist_store = PlaylistStore::new(&app_handle).map_err(std::io::Error::other)?;
+    let playlist = match playlist_store.load() {
+        Ok(Some(playlist)) => playlist,
+        Ok(None) => PlaylistState::new(),
+        Err(e) => {
+            println!("[Playlist] Failed to restore playlist: {}", e);
+            PlaylistState::new()
+        }
+    };
+    let (playlist_save_tx, playlist_save_rx) = mpsc::unbounded_channel();
+    // 종료할 때 마지막 상태를 바로 저장할 수 있도록 따로 등록
+    app.manage(playlist_store.clone());
+    tauri::async_runtime::spawn(run_playlist_saver(playlist_store, playlist_save_rx));
+
+    // YouTube 조회 캐시 (파일이 깨졌으면 빈 캐시로 새로 시작)
+    let cache_path = app_handle.path().app_data_dir()?.join("youtube_cache.json");
+    let youtube_cache = MetadataCache::load(cache_path.clone()).unwrap_or_else(|e| {
+        println!("[YouTube] Failed to load metadata cache: {}", e);
+        MetadataCache::with_path(cache_path)
+    });
+    let (cache_changed_tx, cache_changed_rx) = mpsc::unbounded_channel();
+    let youtube_service = Arc::new(YouTubeService::with_cache(youtube_cache, cache_changed_tx));
+    tauri::async_runtime::spawn(run_youtube_cache_saver(
+        youtube_service.clone(),
+        cache_changed_rx,
+    ));
+    // 종료할 때 남은 변경을 저장할 수 있도록 따로 등록
+    app.manage(youtube_service.clone());
+
+    // 채팅 이벤트 채널
+    let (chat_event_tx, chat_event_rx) = mpsc::unbounded_channel();
 
     // 초기 상태 생성
     let state = Arc::new(RwLock::new(AppState {
         connection_state: ChzzkState::Disconnected,
         chat_instance: None,
+        chat_event_tx,
         ai_config: None,
         ai_service: None,
         chat_buffer: VecDeque::with_capacity(100),
         target_audience: None,
         last_context_analysis: None,
-        playlist: PlaylistState::new(),
+        playlist,
+        playlist_save_tx,
+        vote_skip: VoteSkipTracker::new(),
+        fallback: FallbackPlayer::new(fallback_playlist),
+        playlist_undo: UndoHistory::new(),
+        search_picks: SearchPicks::new(),
         command_parser: CommandParser::new(command_config),
-        youtube_service: YouTubeService::new(),
-        processed_commands: HashSet::new(),
+        youtube_service,
         display_messages: VecDeque::with_capacity(500),
         config_manager,
+        chzzk_config,
+        chzzk_auth,
+        command_count_tx,
     }));
 
+    tauri::async_runtime::spawn(run_chat_event_loop(
+        state.clone(),
+        app_handle.clone(),
+        chat_event_rx,
+    ));
+
     app.manage(state);
 
     Ok(())
 }
 
+// 캐시가 바뀔 때마다 받은 알림을 모아서 한 번만 저장
+async fn run_youtube_cache_saver(
+    youtube_service: Arc<YouTubeService>,
+    mut changed_rx: mpsc::UnboundedReceiver<()>,
+) {
+    while changed_rx.recv().await.is_some() {
+        // 채널이 닫히거나 대기 시간이 지날 때까지 기다림
+        while let Ok(Some(())) = tokio::time::timeout(CACHE_SAVE_DEBOUNCE, changed_rx.recv()).await
+        {
+        }
+
+        if let Err(e) = youtube_service.save_cache() {
+            println!("[YouTube] Failed to save metadata cache: {}", e);
+        }
+    }
+}
+
 // Process playlist command
 async fn process_playlist_command(
     query: String,
     username: String,
+    requester_id: Option<String>,
+    priority: RequestPriority,
+    bypass_rules: bool,
     state: SharedAppState,
     app_handle: AppHandle,
-) -> Result<(), String> {
+) -> Result<Vec<PlaylistItem>, String> {
     println!(
         "[process_playlist_command] Starting: query={}, user={}",
         query, username
     );
 
-    // Get user limit from config
-    let user_limit = {
+    let videos = find_videos(&query, &state).await?;
+
+    // 재생목록 링크는 규칙에 걸린 곡만 건너뛰고 나머지를 추가
+    let mut added = Vec::new();
+    let mut first_error = None;
+    for video in videos {
+        let item = requested_item(video, username.clone(), requester_id.clone(), priority);
+        match add_requested_item(item, bypass_rules, state.clone(), app_handle.clone()).await {
+            Ok(item) => added.push(item),
+            Err(e) => {
+                first_error.get_or_insert(e);
+            }
+        }
+    }
+
+    finish_requested_items(&mut *state.write().await, &added, &app_handle)?;
+
+    match first_error {
+        Some(e) if added.is_empty() => Err(e),
+        _ => Ok(added),
+    }
+}
+
+// 검색어, 영상 링크, 재생목록 링크(설정된 최대 개수까지)로 영상을 찾음
+async fn find_videos(query: &str, state: &SharedAppState) -> Result<Vec<YouTubeVideo>, String> {
+    let (youtube_service, max_playlist_import) = {
         let app_state = state.read().await;
-        app_state.command_parser.config().playlist_limits.user_limit
+        let limits = &app_state.command_parser.config().playlist_limits;
+        (
+            app_state.youtube_service.clone(),
+            limits.max_playlist_import,
+        )
     };
 
-    // Check if query is a YouTube URL
-    if playlist::is_youtube_url(&query) {
-        // Extract video ID
-        if let Some(video_id) = playlist::extract_youtube_id(&query) {
-            // Get video info
-            let video_info = {
-                let app_state = state.read().await;
-                app_state
-                    .youtube_service
-                    .get_video_info_oembed(&video_id)
-                    .await
-            };
+    let link = playlist::link::parse_youtube_ref(query);
 
-            match video_info {
-                Ok(video) => {
-                    // Create playlist item
-                    let item = PlaylistItem {
-                        id: uuid::Uuid::new_v4().to_string(),
-                        video_id: video.video_id,
-                        title: video.title,
-                        channel: video.channel,
-                        duration: video.duration,
-                        thumbnail: video.thumbnail,
-                        url: video.url,
-                        added_by: username,
-                        added_at: chrono::Utc::now().timestamp(),
-                    };
-
-                    // Add to playlist with limit check
-                    let mut app_state = state.write().await;
-                    match app_state
-                        .playlist
-                        .add_item_with_limit(item.clone(), user_limit)
-                    {
-                        Ok(()) => {
-                            // Emit events
-                            app_handle
-                                .emit("playlist:added", &item)
-                                .map_err(|e| e.to_string())?;
-                            app_handle
-                                .emit("playlist:updated", &app_state.playlist)
-                                .map_err(|e| e.to_string())?;
-                            Ok(())
-                        }
-                        Err(e) => {
-                            // Emit error event for user limit reached
-                            app_handle
-                                .emit("playlist:error", &e)
-                                .map_err(|e| e.to_string())?;
-                            Err(e)
-                        }
-                    }
+    // 영상 없이 재생목록만 가리키는 링크는 여러 곡으로 펼침
+    if let Some(YouTubeRef {
+        video_id: None,
+        playlist_id: Some(list_id),
+        ..
+    }) = &link
+    {
+        let videos = youtube_service
+            .get_playlist_videos(list_id, max_playlist_import)
+            .await?;
+
+        // 규칙 검사에 쓰도록 펼친 영상마다 메타데이터를 동시에 확인
+        let videos = futures_util::stream::iter(videos)
+            .map(|mut video| {
+                let youtube_service = youtube_service.clone();
+                async move {
+                    resolve_metadata(&mut video, &youtube_service).await;
+                    video
                 }
-                Err(e) => Err(format!("Failed to get video info: {}", e)),
-            }
-        } else {
-            Err("Invalid YouTube URL".to_string())
+            })
+            .buffered(max_playlist_import.max(1))
+            .collect()
+            .await;
+        return Ok(videos);
+    }
+
+    // Check if query is a YouTube URL or video ID
+    let mut video = if let Some(link) = link {
+        let video_id = link
+            .video_id
+            .ok_or_else(|| "Invalid YouTube URL".to_string())?;
+
+        // Get video info
+        let video = youtube_service
+            .get_video_info_oembed(&video_id)
+            .await
+            .map_err(|e| format!("Failed to get video info: {}", e))?;
+        YouTubeVideo {
+            start_seconds: link.start_seconds,
+            ..video
         }
     } else {
         // Search YouTube
-        let search_results = {
-            let app_state = state.read().await;
-            app_state.youtube_service.search(&query, 1).await
-        };
+        let results = youtube_service
+            .search(query, 1)
+            .await
+            .map_err(|e| format!("Search failed: {}", e))?;
+
+        results
+            .videos
+            .into_iter()
+            .next()
+            .ok_or_else(|| "No search results found".to_string())?
+    };
 
-        match search_results {
-            Ok(results) => {
-                if let Some(video) = results.videos.first() {
-                    // Create playlist item from search result
-                    let item = PlaylistItem {
-                        id: uuid::Uuid::new_v4().to_string(),
-                        video_id: video.video_id.clone(),
-                        title: video.title.clone(),
-                        channel: video.channel.clone(),
-                        duration: video.duration.clone(),
-                        thumbnail: video.thumbnail.clone(),
-                        url: video.url.clone(),
-                        added_by: username,
-                        added_at: chrono::Utc::now().timestamp(),
-                    };
-
-                    // Add to playlist with limit check
-                    let mut app_state = state.write().await;
-                    match app_state
-                        .playlist
-                        .add_item_with_limit(item.clone(), user_limit)
-                    {
-                        Ok(()) => {
-                            // Emit events
-                            app_handle
-                                .emit("playlist:added", &item)
-                                .map_err(|e| e.to_string())?;
-                            app_handle
-                                .emit("playlist:updated", &app_state.playlist)
-                                .map_err(|e| e.to_string())?;
-                            Ok(())
-                        }
-                        Err(e) => {
-                            // Emit error event for user limit reached
-                            app_handle
-                                .emit("playlist:error", &e)
-                                .map_err(|e| e.to_string())?;
-                            Err(e)
-                        }
-                    }
-                } else {
-                    Err("No search results found".to_string())
-                }
+    resolve_metadata(&mut video, &youtube_service).await;
+    Ok(vec![video])
+}
+
+// 영상 페이지에서 길이와 재생 가능 여부를 확인 (페이지를 못 읽어도 신청은 받음)
+async fn resolve_metadata(video: &mut YouTubeVideo, youtube_service: &YouTubeService) {
+    match youtube_service.get_video_metadata(&video.video_id).await {
+        Ok(metadata) => {
+            if let Some(secs) = metadata.duration_secs {
+                video.duration = Some(playlist::format_duration_secs(secs));
             }
-            Err(e) => Err(format!("Search failed: {}", e)),
+            video.metadata = Some(metadata);
         }
+        Err(e) => println!("[Backend] Failed to resolve video metadata: {}", e),
     }
 }
 
+// 검색 결과를 채팅으로 보여 주고 신청자가 번호를 고를 때까지 보관
+async fn start_search_pick(
+    query: &str,
+    sender: &CommandSender,
+    priority: RequestPriority,
+    state: &SharedAppState,
+) -> Result<String, String> {
+    let (config, prefix, youtube_service) = {
+        let app_state = state.read().await;
+        let config = app_state.command_parser.config();
+        (
+            config.search_pick.clone(),
+            config.prefix.clone(),
+            app_state.youtube_service.clone(),
+        )
+    };
+
+    let videos = youtube_service
+        .search(query, config.result_count.max(1))
+        .await
+        .map_err(|e| format!("Search failed: {}", e))?
+        .videos;
+    let choices = playlist::pick::describe_choices(&videos);
+
+    state.write().await.search_picks.start(
+        &sender.user_id,
+        videos,
+        priority,
+        Duration::from_secs(config.timeout_secs),
+    )?;
+    Ok(format!(
+        "{}님, {}초 안에 {}pick 번호로 골라 주세요: {}",
+        sender.nickname, config.timeout_secs, prefix, choices
+    ))
+}
+
+fn requested_item(
+    video: YouTubeVideo,
+    username: String,
+    requester_id: Option<String>,
+    priority: RequestPriority,
+) -> PlaylistItem {
+    PlaylistItem {
+        id: uuid::Uuid::new_v4().to_string(),
+        video_id: video.video_id,
+        title: video.title,
+        channel: video.channel,
+        duration: video.duration,
+        thumbnail: video.thumbnail,
+        url: video.url,
+        added_by: username,
+        requester_id,
+        added_at: chrono::Utc::now().timestamp(),
+        priority,
+        source: ItemSource::Request,
+        metadata: video.metadata,
+        start_seconds: video.start_seconds,
+    }
+}
+
+// 채팅 답장용 곡 이름 ('제목' 또는 '제목' 외 N곡)
+fn describe_items(items: &[PlaylistItem]) -> String {
+    match items {
+        [] => String::new(),
+        [item] => format!("'{}'", item.title),
+        [item, rest @ ..] => format!("'{}' 외 {}곡", item.title, rest.len()),
+    }
+}
+
+// 신청곡 규칙과 유저 당 제한을 확인하고 플레이리스트에 추가 (앱에서 직접 추가한 곡은 bypass_rules)
+async fn add_requested_item(
+    item: PlaylistItem,
+    bypass_rules: bool,
+    state: SharedAppState,
+    app_handle: AppHandle,
+) -> Result<PlaylistItem, String> {
+    let mut app_state = state.write().await;
+    let limits = app_state.command_parser.config().playlist_limits.clone();
+
+    // 앱에서 추가한 곡은 신청곡 규칙을 건너뛰지만 재생할 수 없는 영상은 누구든 거절
+    let checked = playlist::policy::check_playable(&item, &limits.region).and_then(|()| {
+        if bypass_rules {
+            Ok(())
+        } else {
+            playlist::policy::check_request(&limits, &app_state.playlist, &item, item.added_at)
+        }
+    });
+    let checked = checked.map_err(|rejection| rejection.message(&item.title));
+
+    // 앱에서 추가한 곡과 후원 신청곡은 유저 당 제한에서 제외
+    let user_limit = match item.priority {
+        _ if bypass_rules => None,
+        RequestPriority::Donation { .. } => None,
+        _ => limits.user_limit,
+    };
+
+    match checked.and_then(|()| {
+        app_state
+            .playlist
+            .add_item_with_limit(item.clone(), user_limit)
+    }) {
+        Ok(()) => Ok(item),
+        Err(e) => {
+            // 거절 사유를 UI에 알림
+            app_handle
+                .emit("playlist:error", &e)
+                .map_err(|e| e.to_string())?;
+            Err(e)
+        }
+    }
+}
+
+// 한 번의 신청으로 추가된 곡들을 되돌리기 기록 하나로 남기고 UI에 알림
+fn finish_requested_items(
+    app_state: &mut AppState,
+    items: &[PlaylistItem],
+    app_handle: &AppHandle,
+) -> Result<(), String> {
+    if items.is_empty() {
+        return Ok(());
+    }
+    app_state.playlist_undo.record(PlaylistChange::Added {
+        items: items.to_vec(),
+    });
+    let yielded = yield_fallback(app_state, app_handle);
+    emit_playlist_updated(app_state, app_handle)?;
+    yielded?;
+
+    for item in items {
+        app_handle
+            .emit("playlist:added", item)
+            .map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+// 기본 재생목록 곡을 재생 중이면 새 신청곡으로 바로 넘어감
+fn yield_fallback(app_state: &mut AppState, app_handle: &AppHandle) -> Result<(), String> {
+    if !app_state.playlist.is_playing_fallback() {
+        return Ok(());
+    }
+    if let Some(item) = app_state.playlist.next() {
+        app_handle
+            .emit("playlist:play", item)
+            .map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+// 대기 중인 신청곡이 없을 때 기본 재생목록 곡을 재생 (재생할 곡이 없으면 false)
+fn play_fallback(app_state: &mut AppState, app_handle: &AppHandle) -> Result<bool, String> {
+    let Some(item) = app_state.fallback.next_item(chrono::Utc::now().timestamp()) else {
+        return Ok(false);
+    };
+    if let Some(item) = app_state.playlist.play_fallback(item) {
+        app_handle
+            .emit("playlist:play", item)
+            .map_err(|e| e.to_string())?;
+    }
+    Ok(true)
+}
+
+// 플레이리스트 변경을 UI에 알리고 저장을 예약
+fn emit_playlist_updated(app_state: &AppState, app_handle: &AppHandle) -> Result<(), String> {
+    let _ = app_state.playlist_save_tx.send(app_state.playlist.clone());
+    app_handle
+        .emit("playlist:updated", &app_state.playlist)
+        .map_err(|e| e.to_string())
+}
+
 // Playlist control commands
 #[tauri::command]
 async fn get_playlist(state: State<'_, SharedAppState>) -> Result<PlaylistState, String> {
@@ -761,10 +1680,19 @@ async fn move_playlist_item(
     app_handle: AppHandle,
 ) -> Result<(), String> {
     let mut app_state = state.write().await;
+    let id = app_state
+        .playlist
+        .items
+        .get(from)
+        .map(|item| item.id.clone())
+        .ok_or_else(|| "Index out of bounds".to_string())?;
     app_state.playlist.move_item(from, to)?;
-    app_handle
-        .emit("playlist:updated", &app_state.playlist)
-        .map_err(|e| e.to_string())?;
+    if from != to {
+        app_state
+            .playlist_undo
+            .record(PlaylistChange::Moved { id, from, to });
+    }
+    emit_playlist_updated(&app_state, &app_handle)?;
     Ok(())
 }
 
@@ -775,10 +1703,13 @@ async fn remove_playlist_item(
     app_handle: AppHandle,
 ) -> Result<(), String> {
     let mut app_state = state.write().await;
-    app_state.playlist.remove_item(index);
-    app_handle
-        .emit("playlist:updated", &app_state.playlist)
-        .map_err(|e| e.to_string())?;
+    if let Some(item) = app_state.playlist.remove_item(index) {
+        app_state.playlist_undo.record(PlaylistChange::Removed {
+            item: Box::new(item),
+            index,
+        });
+    }
+    emit_playlist_updated(&app_state, &app_handle)?;
     Ok(())
 }
 
@@ -789,13 +1720,19 @@ async fn play_at_index(
     app_handle: AppHandle,
 ) -> Result<(), String> {
     let mut app_state = state.write().await;
+    let previous = app_state
+        .playlist
+        .current_item()
+        .map(|item| item.id.clone());
     if let Some(item) = app_state.playlist.play_at(index) {
+        let next = item.id.clone();
         app_handle
             .emit("playlist:play", item)
             .map_err(|e| e.to_string())?;
-        app_handle
-            .emit("playlist:updated", &app_state.playlist)
-            .map_err(|e| e.to_string())?;
+        app_state
+            .playlist_undo
+            .record(PlaylistChange::PlayedAt { previous, next });
+        emit_playlist_updated(&app_state, &app_handle)?;
         Ok(())
     } else {
         Err("Invalid playlist index".to_string())
@@ -808,24 +1745,48 @@ async fn skip_to_next(state: SharedAppState, app_handle: AppHandle) -> Result<()
         app_handle
             .emit("playlist:play", item)
             .map_err(|e| e.to_string())?;
-        app_handle
-            .emit("playlist:updated", &app_state.playlist)
-            .map_err(|e| e.to_string())?;
+        emit_playlist_updated(&app_state, &app_handle)?;
         Ok(())
+    } else if play_fallback(&mut app_state, &app_handle)? {
+        emit_playlist_updated(&app_state, &app_handle)
     } else {
         Err("No next item in playlist".to_string())
     }
 }
 
-async fn go_to_previous(state: SharedAppState, app_handle: AppHandle) -> Result<(), String> {
+// 곡 재생이 끝났을 때 재생 모드에 따라 다음 곡 재생
+async fn play_after_track_ended(
+    state: SharedAppState,
+    app_handle: AppHandle,
+) -> Result<(), String> {
     let mut app_state = state.write().await;
-    if let Some(item) = app_state.playlist.previous() {
+    if let Some(item) = app_state.playlist.advance() {
         app_handle
             .emit("playlist:play", item)
             .map_err(|e| e.to_string())?;
+    } else if !play_fallback(&mut app_state, &app_handle)? {
+        app_state.playlist.is_playing = false;
+    }
+    emit_playlist_updated(&app_state, &app_handle)
+}
+
+async fn change_playback_mode(
+    mode: PlaybackMode,
+    state: SharedAppState,
+    app_handle: AppHandle,
+) -> Result<(), String> {
+    let mut app_state = state.write().await;
+    app_state.playlist.set_mode(mode);
+    emit_playlist_updated(&app_state, &app_handle)
+}
+
+async fn go_to_previous(state: SharedAppState, app_handle: AppHandle) -> Result<(), String> {
+    let mut app_state = state.write().await;
+    if let Some(item) = app_state.playlist.previous() {
         app_handle
-            .emit("playlist:updated", &app_state.playlist)
+            .emit("playlist:play", item)
             .map_err(|e| e.to_string())?;
+        emit_playlist_updated(&app_state, &app_handle)?;
         Ok(())
     } else {
         Err("No previous item in playlist".to_string())
@@ -838,9 +1799,7 @@ async fn pause_playback(state: SharedAppState, app_handle: AppHandle) -> Result<
     app_handle
         .emit("playlist:pause", ())
         .map_err(|e| e.to_string())?;
-    app_handle
-        .emit("playlist:updated", &app_state.playlist)
-        .map_err(|e| e.to_string())?;
+    emit_playlist_updated(&app_state, &app_handle)?;
     Ok(())
 }
 
@@ -850,21 +1809,23 @@ async fn resume_playback(state: SharedAppState, app_handle: AppHandle) -> Result
     app_handle
         .emit("playlist:resume", ())
         .map_err(|e| e.to_string())?;
-    app_handle
-        .emit("playlist:updated", &app_state.playlist)
-        .map_err(|e| e.to_string())?;
+    emit_playlist_updated(&app_state, &app_handle)?;
     Ok(())
 }
 
 async fn clear_playlist(state: SharedAppState, app_handle: AppHandle) -> Result<(), String> {
     let mut app_state = state.write().await;
+    let change = PlaylistChange::Cleared {
+        items: app_state.playlist.items.clone(),
+        current_index: app_state.playlist.current_index,
+        started_id: app_state.playlist.started_id.clone(),
+    };
     app_state.playlist.clear();
+    app_state.playlist_undo.record(change);
     app_handle
         .emit("playlist:cleared", ())
         .map_err(|e| e.to_string())?;
-    app_handle
-        .emit("playlist:updated", &app_state.playlist)
-        .map_err(|e| e.to_string())?;
+    emit_playlist_updated(&app_state, &app_handle)?;
     Ok(())
 }
 
@@ -876,12 +1837,61 @@ async fn set_autoplay(
 ) -> Result<(), String> {
     let mut app_state = state.write().await;
     app_state.playlist.set_autoplay(enabled);
-    app_handle
-        .emit("playlist:updated", &app_state.playlist)
-        .map_err(|e| e.to_string())?;
+    emit_playlist_updated(&app_state, &app_handle)?;
     Ok(())
 }
 
+#[tauri::command]
+async fn get_play_history(
+    limit: Option<usize>,
+    state: State<'_, SharedAppState>,
+) -> Result<Vec<PlayedItem>, String> {
+    let app_state = state.read().await;
+    let limit = limit.unwrap_or(app_state.playlist.history.len());
+    Ok(app_state.playlist.recent_history(limit))
+}
+
+#[tauri::command]
+async fn clear_play_history(
+    state: State<'_, SharedAppState>,
+    app_handle: AppHandle,
+) -> Result<(), String> {
+    let mut app_state = state.write().await;
+    app_state.playlist.clear_history();
+    emit_playlist_updated(&app_state, &app_handle)
+}
+
+#[tauri::command]
+async fn set_remove_finished(
+    enabled: bool,
+    state: State<'_, SharedAppState>,
+    app_handle: AppHandle,
+) -> Result<(), String> {
+    let mut app_state = state.write().await;
+    app_state.playlist.set_remove_finished(enabled);
+    emit_playlist_updated(&app_state, &app_handle)
+}
+
+#[tauri::command]
+async fn set_fair_queue(
+    enabled: bool,
+    state: State<'_, SharedAppState>,
+    app_handle: AppHandle,
+) -> Result<(), String> {
+    let mut app_state = state.write().await;
+    app_state.playlist.set_fair_queue(enabled);
+    emit_playlist_updated(&app_state, &app_handle)
+}
+
+#[tauri::command]
+async fn set_playback_mode(
+    mode: PlaybackMode,
+    state: State<'_, SharedAppState>,
+    app_handle: AppHandle,
+) -> Result<(), String> {
+    change_playback_mode(mode, state.inner().clone(), app_handle).await
+}
+
 #[tauri::command]
 async fn get_command_config(state: State<'_, SharedAppState>) -> Result<CommandConfig, String> {
     let app_state = state.read().await;
@@ -893,7 +1903,15 @@ async fn update_command_config(
     config: CommandConfig,
     state: State<'_, SharedAppState>,
 ) -> Result<(), String> {
+    let mut config = config;
     let mut app_state = state.write().await;
+    // 사용자 정의 명령어는 별도 명령으로 관리하므로 현재 값을 유지
+    config.custom_commands = app_state.command_parser.config().custom_commands.clone();
+    save_command_config(&mut app_state, config)
+}
+
+// 명령어 설정을 파일에 저장하고 파서에 반영
+fn save_command_config(app_state: &mut AppState, config: CommandConfig) -> Result<(), String> {
     // 설정을 파일에 저장
     app_state
         .config_manager
@@ -903,16 +1921,85 @@ async fn update_command_config(
     Ok(())
 }
 
+#[tauri::command]
+async fn get_custom_commands(
+    state: State<'_, SharedAppState>,
+) -> Result<Vec<CustomCommand>, String> {
+    let app_state = state.read().await;
+    let mut commands: Vec<CustomCommand> = app_state
+        .command_parser
+        .config()
+        .custom_commands
+        .values()
+        .cloned()
+        .collect();
+    commands.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(commands)
+}
+
+#[tauri::command]
+async fn add_custom_command(
+    command: CustomCommand,
+    state: State<'_, SharedAppState>,
+) -> Result<(), String> {
+    let mut app_state = state.write().await;
+    let mut config = app_state.command_parser.config().clone();
+    config.validate_custom_command(&command, None)?;
+
+    config.custom_commands.insert(command.name.clone(), command);
+    save_command_config(&mut app_state, config)
+}
+
+#[tauri::command]
+async fn update_custom_command(
+    name: String,
+    command: CustomCommand,
+    state: State<'_, SharedAppState>,
+) -> Result<(), String> {
+    let mut app_state = state.write().await;
+    let mut config = app_state.command_parser.config().clone();
+    let previous = config
+        .custom_commands
+        .remove(&name)
+        .ok_or_else(|| format!("Custom command not found: {}", name))?;
+    config.validate_custom_command(&command, Some(&name))?;
+
+    // 사용 횟수는 유지
+    let command = CustomCommand {
+        count: previous.count,
+        ..command
+    };
+    config.custom_commands.insert(command.name.clone(), command);
+    save_command_config(&mut app_state, config)
+}
+
+#[tauri::command]
+async fn remove_custom_command(
+    name: String,
+    state: State<'_, SharedAppState>,
+) -> Result<(), String> {
+    let mut app_state = state.write().await;
+    let mut config = app_state.command_parser.config().clone();
+    config
+        .custom_commands
+        .remove(&name)
+        .ok_or_else(|| format!("Custom command not found: {}", name))?;
+    save_command_config(&mut app_state, config)
+}
+
 #[tauri::command]
 async fn add_to_playlist_direct(
     query: String,
     state: State<'_, SharedAppState>,
     app_handle: AppHandle,
 ) -> Result<String, String> {
-    // Use "App User" as the username for direct additions
+    // 앱에서 직접 추가한 곡은 신청곡 규칙을 적용하지 않음
     process_playlist_command(
         query,
         "App User".to_string(),
+        None,
+        RequestPriority::Normal,
+        true,
         state.inner().clone(),
         app_handle,
     )
@@ -920,6 +2007,92 @@ async fn add_to_playlist_direct(
     Ok("Successfully added to playlist".to_string())
 }
 
+#[tauri::command]
+async fn export_playlist(
+    format: PlaylistFormat,
+    state: State<'_, SharedAppState>,
+) -> Result<String, String> {
+    let app_state = state.read().await;
+    playlist::transfer::export_items(&app_state.playlist.items, format)
+}
+
+// 가져온 곡은 대기열 끝에 추가하고 추가된 곡 수를 반환
+#[tauri::command]
+async fn import_playlist(
+    content: String,
+    format: PlaylistFormat,
+    state: State<'_, SharedAppState>,
+    app_handle: AppHandle,
+) -> Result<usize, String> {
+    let items = playlist::transfer::import_items(
+        &content,
+        format,
+        "App User",
+        chrono::Utc::now().timestamp(),
+    )?;
+
+    let mut app_state = state.write().await;
+    let count = items.len();
+    for item in items.iter().cloned() {
+        app_state.playlist.add_item(item);
+    }
+    app_state
+        .playlist_undo
+        .record(PlaylistChange::Added { items });
+    yield_fallback(&mut app_state, &app_handle)?;
+    emit_playlist_updated(&app_state, &app_handle)?;
+    Ok(count)
+}
+
+#[tauri::command]
+async fn get_fallback_playlist(
+    state: State<'_, SharedAppState>,
+) -> Result<FallbackPlaylist, String> {
+    let app_state = state.read().await;
+    Ok(app_state.fallback.playlist.clone())
+}
+
+#[tauri::command]
+async fn update_fallback_playlist(
+    fallback: FallbackPlaylist,
+    state: State<'_, SharedAppState>,
+) -> Result<(), String> {
+    let mut app_state = state.write().await;
+    app_state
+        .config_manager
+        .update_fallback_playlist(fallback.clone())?;
+    app_state.fallback.set_playlist(fallback);
+    Ok(())
+}
+
+// 검색어나 링크로 찾은 곡을 기본 재생목록 끝에 추가
+#[tauri::command]
+async fn add_to_fallback_playlist(
+    query: String,
+    state: State<'_, SharedAppState>,
+) -> Result<FallbackPlaylist, String> {
+    let videos = find_videos(&query, state.inner()).await?;
+
+    let mut app_state = state.write().await;
+    let mut fallback = app_state.fallback.playlist.clone();
+    fallback.items.extend(
+        videos.into_iter().map(|video| {
+            requested_item(video, "App User".to_string(), None, RequestPriority::Normal)
+        }),
+    );
+    app_state
+        .config_manager
+        .update_fallback_playlist(fallback.clone())?;
+    app_state.fallback.set_playlist(fallback.clone());
+    Ok(fallback)
+}
+
+// YouTube 조회 캐시 적중 통계
+#[tauri::command]
+async fn get_youtube_cache_stats(state: State<'_, SharedAppState>) -> Result<CacheStats, String> {
+    Ok(state.read().await.youtube_service.cache_stats())
+}
+
 #[tauri::command]
 async fn search_youtube(
     query: String,
@@ -930,8 +2103,8 @@ async fn search_youtube(
         return Err("Search query cannot be empty".to_string());
     }
 
-    let app_state = state.read().await;
-    let results = app_state.youtube_service.search(&query, limit).await?;
+    let youtube_service = state.read().await.youtube_service.clone();
+    let results = youtube_service.search(&query, limit).await?;
 
     Ok(results.videos)
 }
@@ -944,6 +2117,64 @@ async fn skip_to_next_command(
     skip_to_next(state.inner().clone(), app_handle).await
 }
 
+#[tauri::command]
+async fn track_ended_command(
+    state: State<'_, SharedAppState>,
+    app_handle: AppHandle,
+) -> Result<(), String> {
+    play_after_track_ended(state.inner().clone(), app_handle).await
+}
+
+// 마지막 플레이리스트 변경을 되돌리거나(redo면 다시 적용) 그 내용을 반환
+async fn undo_playlist_change(
+    redo: bool,
+    state: SharedAppState,
+    app_handle: AppHandle,
+) -> Result<String, String> {
+    let mut guard = state.write().await;
+    let app_state = &mut *guard;
+    let before = app_state
+        .playlist
+        .current_item()
+        .map(|item| item.id.clone());
+    let description = if redo {
+        app_state.playlist_undo.redo(&mut app_state.playlist)?
+    } else {
+        app_state.playlist_undo.undo(&mut app_state.playlist)?
+    };
+
+    // 재생 중인 곡이 바뀌었으면 플레이어에도 알림
+    if let Some(item) = app_state
+        .playlist
+        .current_item()
+        .filter(|item| before.as_ref() != Some(&item.id))
+    {
+        if app_state.playlist.is_playing {
+            app_handle
+                .emit("playlist:play", item)
+                .map_err(|e| e.to_string())?;
+        }
+    }
+    emit_playlist_updated(app_state, &app_handle)?;
+    Ok(description)
+}
+
+#[tauri::command]
+async fn undo_playlist_command(
+    state: State<'_, SharedAppState>,
+    app_handle: AppHandle,
+) -> Result<String, String> {
+    undo_playlist_change(false, state.inner().clone(), app_handle).await
+}
+
+#[tauri::command]
+async fn redo_playlist_command(
+    state: State<'_, SharedAppState>,
+    app_handle: AppHandle,
+) -> Result<String, String> {
+    undo_playlist_change(true, state.inner().clone(), app_handle).await
+}
+
 #[tauri::command]
 async fn clear_playlist_command(
     state: State<'_, SharedAppState>,
@@ -990,9 +2221,13 @@ fn build_app() -> tauri::Builder<tauri::Wry> {
         .setup(setup)
         .invoke_handler(tauri::generate_handler![
             connect_chzzk_chat,
+            watch_chzzk_channel,
             disconnect_chzzk_chat,
             is_chzzk_connected,
             get_chzzk_state,
+            get_chzzk_config,
+            update_chzzk_config,
+            update_chzzk_auth,
             configure_ai,
             set_target_audience,
             add_chat_message,
@@ -1004,12 +2239,31 @@ fn build_app() -> tauri::Builder<tauri::Wry> {
             remove_playlist_item,
             play_at_index,
             set_autoplay,
+            set_playback_mode,
+            get_play_history,
+            clear_play_history,
+            set_remove_finished,
+            set_fair_queue,
             get_command_config,
             update_command_config,
+            send_chat_message,
+            get_custom_commands,
+            add_custom_command,
+            update_custom_command,
+            remove_custom_command,
             add_to_playlist_direct,
+            export_playlist,
+            import_playlist,
+            get_fallback_playlist,
+            update_fallback_playlist,
+            add_to_fallback_playlist,
             search_youtube,
+            get_youtube_cache_stats,
             skip_to_next_command,
+            track_ended_command,
             clear_playlist_command,
+            undo_playlist_command,
+            redo_playlist_command,
             get_chat_messages,
             store_display_message,
             clear_display_messages
@@ -1019,6 +2273,44 @@ fn build_app() -> tauri::Builder<tauri::Wry> {
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     build_app()
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app_handle, event| {
+            if let tauri::RunEvent::Exit = event {
+                save_on_exit(app_handle);
+            }
+        });
+}
+
+// 모아서 저장하느라 아직 파일에 쓰지 않은 변경을 종료 전에 저장
+fn save_on_exit(app_handle: &AppHandle) {
+    if let Some(youtube_service) = app_handle.try_state::<Arc<YouTubeService>>() {
+        if let Err(e) = youtube_service.save_cache() {
+            println!("[YouTube] Failed to save metadata cache: {}", e);
+        }
+    }
+
+    let Some(state) = app_handle.try_state::<SharedAppState>() else {
+        return;
+    };
+    let app_state = state.blocking_read();
+    if let Some(playlist_store) = app_handle.try_state::<PlaylistStore>() {
+        if let Err(e) = playlist_store.save(&app_state.playlist) {
+            println!("[Playlist] Failed to save playlist: {}", e);
+        }
+    }
+
+    let counts = app_state
+        .command_parser
+        .config()
+        .custom_commands
+        .iter()
+        .map(|(name, command)| (name.clone(), command.count))
+        .collect();
+    if let Err(e) = app_state
+        .config_manager
+        .update_custom_command_counts(&counts)
+    {
+        println!("[Config] Failed to save custom command counts: {}", e);
+    }
 }
@@ -0,0 +1,112 @@
+use super::{ItemSource, PlaylistItem, RequestPriority};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+// 신청곡이 없을 때 재생할 스트리머의 기본 재생목록 (설정 파일에 저장)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FallbackPlaylist {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub shuffle: bool,
+    #[serde(default)]
+    pub items: Vec<PlaylistItem>,
+}
+
+// 기본 재생목록에서 다음에 재생할 곡을 고름
+#[derive(Debug, Default)]
+pub struct FallbackPlayer {
+    pub playlist: FallbackPlaylist,
+    last_index: Option<usize>,
+}
+
+impl FallbackPlayer {
+    pub fn new(playlist: FallbackPlaylist) -> Self {
+        Self {
+            playlist,
+            last_index: None,
+        }
+    }
+
+    pub fn set_playlist(&mut self, playlist: FallbackPlaylist) {
+        self.playlist = playlist;
+        self.last_index = None;
+    }
+
+    // 순서대로 돌거나, 셔플이면 방금 재생한 곡을 빼고 무작위로 선택
+    pub fn next_item(&mut self, now: i64) -> Option<PlaylistItem> {
+        let len = self.playlist.items.len();
+        if !self.playlist.enabled || len == 0 {
+            return None;
+        }
+
+        let index = if self.playlist.shuffle && len > 1 {
+            let mut rng = rand::thread_rng();
+            loop {
+                let index = rng.gen_range(0..len);
+                if Some(index) != self.last_index {
+                    break index;
+                }
+            }
+        } else {
+            self.last_index.map_or(0, |last| (last + 1) % len)
+        };
+        self.last_index = Some(index);
+
+        Some(PlaylistItem {
+            id: uuid::Uuid::new_v4().to_string(),
+            requester_id: None,
+            added_at: now,
+            priority: RequestPriority::Normal,
+            source: ItemSource::Fallback,
+            ..self.playlist.items[index].clone()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::playlist::test_item;
+
+    fn fallback_with(ids: &[&str], shuffle: bool) -> FallbackPlayer {
+        FallbackPlayer::new(FallbackPlaylist {
+            enabled: true,
+            shuffle,
+            items: ids.iter().map(|id| test_item(id)).collect(),
+        })
+    }
+
+    #[test]
+    fn test_plays_in_order_and_wraps() {
+        let mut player = fallback_with(&["a", "b"], false);
+        let played: Vec<String> = (0..3)
+            .map(|_| player.next_item(0).unwrap().video_id)
+            .collect();
+        assert_eq!(played, ["a", "b", "a"]);
+
+        let item = player.next_item(100).unwrap();
+        assert_eq!(item.source, ItemSource::Fallback);
+        assert_eq!(item.added_at, 100);
+        assert_ne!(item.id, "b");
+    }
+
+    #[test]
+    fn test_shuffle_does_not_repeat_last() {
+        let mut player = fallback_with(&["a", "b", "c"], true);
+        let mut last = player.next_item(0).unwrap().video_id;
+        for _ in 0..20 {
+            let next = player.next_item(0).unwrap().video_id;
+            assert_ne!(next, last);
+            last = next;
+        }
+    }
+
+    #[test]
+    fn test_disabled_or_empty() {
+        let mut player = fallback_with(&["a"], false);
+        player.playlist.enabled = false;
+        assert!(player.next_item(0).is_none());
+        assert!(FallbackPlayer::default().next_item(0).is_none());
+    }
+}
@@ -0,0 +1,214 @@
+use url::Url;
+
+// YouTube 링크에서 읽은 영상/재생목록과 시작 위치
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct YouTubeRef {
+    pub video_id: Option<String>,
+    pub start_seconds: Option<u64>,
+    pub playlist_id: Option<String>,
+}
+
+const YOUTUBE_HOSTS: [&str; 6] = [
+    "youtube.com",
+    "www.youtube.com",
+    "m.youtube.com",
+    "music.youtube.com",
+    "youtube-nocookie.com",
+    "www.youtube-nocookie.com",
+];
+
+// 경로 첫 부분 뒤에 영상 ID가 오는 주소 (/embed/ID, /shorts/ID ...)
+const VIDEO_PATHS: [&str; 4] = ["embed", "shorts", "live", "v"];
+
+fn is_id(value: &str) -> bool {
+    !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+// 링크만 인식 (채팅 메시지에서 링크를 찾을 때 사용)
+pub fn parse_youtube_url(text: &str) -> Option<YouTubeRef> {
+    let text = text.trim();
+    // 스킴 없이 붙여넣은 주소도 받음
+    let url = Url::parse(text)
+        .or_else(|_| Url::parse(&format!("https://{}", text)))
+        .ok()?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return None;
+    }
+    let host = url.host_str()?.to_ascii_lowercase();
+
+    let query = |key: &str| {
+        url.query_pairs()
+            .find(|(name, _)| name == key)
+            .map(|(_, value)| value.into_owned())
+    };
+    let mut segments = url.path_segments()?.filter(|segment| !segment.is_empty());
+
+    let video_id = if host == "youtu.be" {
+        segments.next().map(str::to_string)
+    } else if YOUTUBE_HOSTS.contains(&host.as_str()) {
+        match segments.next() {
+            Some("watch") => query("v"),
+            Some("playlist") => None,
+            Some(kind) if VIDEO_PATHS.contains(&kind) => segments.next().map(str::to_string),
+            _ => return None,
+        }
+    } else {
+        return None;
+    };
+
+    let video_id = video_id.filter(|id| is_id(id));
+    let playlist_id = query("list").filter(|id| is_id(id));
+    if video_id.is_none() && playlist_id.is_none() {
+        return None;
+    }
+
+    // ?t=1m30s, ?start=90, #t=90 순으로 확인
+    let fragment_time = url
+        .fragment()
+        .and_then(|fragment| fragment.strip_prefix("t="))
+        .map(str::to_string);
+    let start_seconds = query("t")
+        .or_else(|| query("start"))
+        .or(fragment_time)
+        .and_then(|time| parse_timestamp(&time))
+        .filter(|&secs| secs > 0);
+
+    Some(YouTubeRef {
+        video_id,
+        start_seconds,
+        playlist_id,
+    })
+}
+
+// 링크 또는 11자리 영상 ID
+pub fn parse_youtube_ref(text: &str) -> Option<YouTubeRef> {
+    let text = text.trim();
+    if looks_like_video_id(text) {
+        return Some(YouTubeRef {
+            video_id: Some(text.to_string()),
+            ..YouTubeRef::default()
+        });
+    }
+    parse_youtube_url(text)
+}
+
+// 영어 단어 검색어(Butterflies 등)를 ID로 착각하지 않도록 둘째 글자 이후 대문자가 있어야 ID로 봄
+fn looks_like_video_id(text: &str) -> bool {
+    text.len() == 11
+        && is_id(text)
+        && text.chars().skip(1).any(|c| c.is_ascii_uppercase())
+        && text
+            .chars()
+            .any(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+}
+
+// 90, 90s, 1m30s, 1h2m3s, 1:30 형식의 시간
+pub fn parse_timestamp(text: &str) -> Option<u64> {
+    let text = text.trim();
+    if text.contains(':') {
+        return super::parse_duration_secs(text);
+    }
+    if let Ok(secs) = text.parse::<u64>() {
+        return Some(secs);
+    }
+
+    let mut total = 0;
+    let mut number = String::new();
+    for c in text.chars() {
+        if c.is_ascii_digit() {
+            number.push(c);
+            continue;
+        }
+        let unit = match c {
+            'h' => 3600,
+            'm' => 60,
+            's' => 1,
+            _ => return None,
+        };
+        total += number.parse::<u64>().ok()? * unit;
+        number.clear();
+    }
+    number.is_empty().then_some(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn video(text: &str) -> Option<String> {
+        parse_youtube_ref(text).and_then(|link| link.video_id)
+    }
+
+    #[test]
+    fn test_url_shapes() {
+        let id = Some("dQw4w9WgXcQ".to_string());
+        for url in [
+            "https://www.youtube.com/watch?v=dQw4w9WgXcQ",
+            "https://www.youtube.com/watch?feature=share&v=dQw4w9WgXcQ",
+            "youtube.com/watch?v=dQw4w9WgXcQ",
+            "https://m.youtube.com/watch?v=dQw4w9WgXcQ",
+            "https://music.youtube.com/watch?v=dQw4w9WgXcQ&si=abc",
+            "https://youtu.be/dQw4w9WgXcQ?si=abc",
+            "https://www.youtube.com/embed/dQw4w9WgXcQ",
+            "https://www.youtube-nocookie.com/embed/dQw4w9WgXcQ?rel=0",
+            "https://www.youtube.com/live/dQw4w9WgXcQ?feature=shared",
+            "https://youtube.com/shorts/dQw4w9WgXcQ",
+            "dQw4w9WgXcQ",
+        ] {
+            assert_eq!(video(url), id, "{}", url);
+        }
+
+        assert_eq!(video("https://example.com/watch?v=dQw4w9WgXcQ"), None);
+        assert_eq!(video("https://www.youtube.com/@channel"), None);
+        assert_eq!(video("ftp://youtube.com/watch?v=dQw4w9WgXcQ"), None);
+        // 검색어로 쓰일 만한 11글자 단어는 ID가 아님
+        assert_eq!(video("Butterflies"), None);
+        assert_eq!(video("hello_world"), None);
+        assert!(parse_youtube_url("dQw4w9WgXcQ").is_none());
+    }
+
+    #[test]
+    fn test_start_and_playlist() {
+        let link =
+            parse_youtube_url("https://www.youtube.com/watch?v=dQw4w9WgXcQ&list=PLabc&t=1m30s")
+                .unwrap();
+        assert_eq!(
+            link,
+            YouTubeRef {
+                video_id: Some("dQw4w9WgXcQ".to_string()),
+                start_seconds: Some(90),
+                playlist_id: Some("PLabc".to_string()),
+            }
+        );
+
+        let start = |url| parse_youtube_url(url).unwrap().start_seconds;
+        assert_eq!(start("https://youtu.be/dQw4w9WgXcQ?t=42"), Some(42));
+        assert_eq!(
+            start("https://www.youtube.com/embed/dQw4w9WgXcQ?start=7"),
+            Some(7)
+        );
+        assert_eq!(
+            start("https://www.youtube.com/watch?v=dQw4w9WgXcQ#t=1h2s"),
+            Some(3602)
+        );
+        assert_eq!(start("https://youtu.be/dQw4w9WgXcQ?t=0"), None);
+        assert_eq!(start("https://youtu.be/dQw4w9WgXcQ?t=abc"), None);
+
+        let list = parse_youtube_url("https://www.youtube.com/playlist?list=PLabc_123-x").unwrap();
+        assert_eq!(list.video_id, None);
+        assert_eq!(list.playlist_id.as_deref(), Some("PLabc_123-x"));
+    }
+
+    #[test]
+    fn test_parse_timestamp() {
+        assert_eq!(parse_timestamp("90"), Some(90));
+        assert_eq!(parse_timestamp("90s"), Some(90));
+        assert_eq!(parse_timestamp("2m"), Some(120));
+        assert_eq!(parse_timestamp("1:02:03"), Some(3723));
+        assert_eq!(parse_timestamp("1m30"), None);
+        assert_eq!(parse_timestamp("1x"), None);
+    }
+}
@@ -1,5 +1,21 @@
+pub mod fallback;
+pub mod link;
+pub mod pick;
+pub mod policy;
+pub mod store;
+pub mod transfer;
+pub mod undo;
+pub mod voteskip;
+
+use crate::youtube::VideoMetadata;
+use rand::seq::SliceRandom;
 use serde::{Deserialize, Serialize};
-use std::collections::VecDeque;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+// 셔플 재생 기록은 이 개수까지만 유지
+const SHUFFLE_HISTORY_LIMIT: usize = 500;
+// 재생 기록 최대 개수
+const PLAY_HISTORY_LIMIT: usize = 100;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PlaylistItem {
@@ -11,7 +27,90 @@ pub struct PlaylistItem {
     pub thumbnail: Option<String>,
     pub url: String,
     pub added_by: String,
+    // 신청자의 채팅 uid (닉네임은 겹치거나 바뀔 수 있음, 앱에서 추가한 곡은 None)
+    #[serde(default)]
+    pub requester_id: Option<String>,
     pub added_at: i64,
+    #[serde(default)]
+    pub priority: RequestPriority,
+    #[serde(default)]
+    pub source: ItemSource,
+    // 영상 페이지에서 확인한 정보 (확인하지 못했으면 None)
+    #[serde(default)]
+    pub metadata: Option<VideoMetadata>,
+    // 링크의 t= 값 (이 위치부터 재생)
+    #[serde(default)]
+    pub start_seconds: Option<u64>,
+}
+
+// 시청자 신청곡인지, 신청곡이 없을 때 재생하는 기본 재생목록 곡인지
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ItemSource {
+    #[default]
+    Request,
+    Fallback,
+}
+
+// 신청곡 우선순위와 그 근거 (높을수록 먼저 재생)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "source", rename_all = "snake_case")]
+pub enum RequestPriority {
+    #[default]
+    Normal,
+    Subscriber,
+    Donation {
+        amount: i32,
+    },
+}
+
+impl RequestPriority {
+    fn rank(&self) -> u8 {
+        match self {
+            Self::Normal => 0,
+            Self::Subscriber => 1,
+            Self::Donation { .. } => 2,
+        }
+    }
+}
+
+// 재생을 시작한 곡과 시작 시각 (신청자는 item.added_by)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlayedItem {
+    pub item: PlaylistItem,
+    pub played_at: i64,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PlaybackMode {
+    #[default]
+    NoRepeat,
+    RepeatAll,
+    RepeatOne,
+    Shuffle,
+}
+
+impl PlaybackMode {
+    // 채팅 명령어 인자로 모드 찾기
+    pub fn from_arg(arg: &str) -> Option<Self> {
+        match arg.trim().to_lowercase().as_str() {
+            "off" | "none" | "norepeat" | "반복없음" => Some(Self::NoRepeat),
+            "all" | "repeat" | "전체반복" => Some(Self::RepeatAll),
+            "one" | "single" | "한곡반복" => Some(Self::RepeatOne),
+            "shuffle" | "random" | "셔플" => Some(Self::Shuffle),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::NoRepeat => "반복 없음",
+            Self::RepeatAll => "전체 반복",
+            Self::RepeatOne => "한 곡 반복",
+            Self::Shuffle => "셔플",
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -20,6 +119,29 @@ pub struct PlaylistState {
     pub current_index: Option<usize>,
     pub is_playing: bool,
     pub autoplay: bool,
+    #[serde(default)]
+    pub mode: PlaybackMode,
+    // 셔플 모드에서 실제로 재생한 순서 (항목 ID, 마지막이 현재 곡)
+    #[serde(default)]
+    pub shuffle_history: Vec<String>,
+    // shuffle_history 중 이번 회차가 시작된 위치
+    #[serde(default)]
+    pub shuffle_cycle_start: usize,
+    // 최근 재생 기록 (마지막이 가장 최근)
+    #[serde(default)]
+    pub history: VecDeque<PlayedItem>,
+    // 다음 곡으로 넘어갈 때 재생이 끝난 곡을 목록에서 제거
+    #[serde(default)]
+    pub remove_finished: bool,
+    // 대기열을 신청자별로 번갈아 가며 정렬
+    #[serde(default)]
+    pub fair_queue: bool,
+    // 직접 옮겨서 공평 정렬에서도 자리가 고정된 항목 ID
+    #[serde(default)]
+    pub pinned: HashSet<String>,
+    // 재생을 시작한 곡의 항목 ID (재생 기록을 지워도 유지)
+    #[serde(default)]
+    pub started_id: Option<String>,
 }
 
 impl PlaylistState {
@@ -29,36 +151,158 @@ impl PlaylistState {
             current_index: None,
             is_playing: false,
             autoplay: true,
+            mode: PlaybackMode::default(),
+            shuffle_history: Vec::new(),
+            shuffle_cycle_start: 0,
+            history: VecDeque::new(),
+            remove_finished: false,
+            fair_queue: false,
+            pinned: HashSet::new(),
+            started_id: None,
+        }
+    }
+
+    pub fn current_item(&self) -> Option<&PlaylistItem> {
+        self.current_index.and_then(|index| self.items.get(index))
+    }
+
+    // 현재 곡의 재생이 이미 시작되었는지
+    fn current_started(&self) -> bool {
+        match (self.current_item(), &self.started_id) {
+            (Some(current), Some(started)) => &current.id == started,
+            _ => false,
+        }
+    }
+
+    // 아직 재생하지 않은 곡 (현재 곡은 재생을 시작하기 전이면 포함)
+    pub fn upcoming(&self) -> Vec<&PlaylistItem> {
+        if self.mode == PlaybackMode::Shuffle {
+            let played: HashSet<&str> = self.shuffle_history[self.shuffle_cycle_start..]
+                .iter()
+                .map(String::as_str)
+                .collect();
+            return self
+                .items
+                .iter()
+                .filter(|item| !played.contains(item.id.as_str()))
+                .collect();
+        }
+
+        let start = match self.current_index {
+            Some(current) if self.current_started() => current + 1,
+            Some(current) => current,
+            None => 0,
+        };
+        self.items.iter().skip(start).collect()
+    }
+
+    // 우선순위 신청곡은 대기열에서 더 낮은 순위의 곡 앞에 넣음 (같은 순위끼리는 신청 순서)
+    fn insert_position(&self, priority: RequestPriority) -> usize {
+        if priority.rank() == 0 {
+            return self.items.len();
         }
+
+        let start = match self.current_index {
+            Some(current) if self.current_started() => current + 1,
+            Some(current) => current,
+            None => 0,
+        };
+        (start..self.items.len())
+            .find(|&i| self.items[i].priority.rank() < priority.rank())
+            .unwrap_or(self.items.len())
+    }
+
+    // 최근 재생 기록 (가장 최근 곡부터)
+    pub fn recent_history(&self, limit: usize) -> Vec<PlayedItem> {
+        self.history.iter().rev().take(limit).cloned().collect()
+    }
+
+    // 지금 재생 중인 곡을 제외한 가장 최근 재생 곡
+    pub fn last_played(&self) -> Option<&PlayedItem> {
+        let skip = usize::from(self.current_started());
+        self.history.iter().rev().nth(skip)
     }
 
     pub fn add_item(&mut self, item: PlaylistItem) {
-        self.items.push_back(item);
+        // 공평 순서에서는 뒤에 붙인 다음 전체를 다시 정렬 (고정된 자리가 밀리지 않도록)
+        let position = if self.fair_queue {
+            self.items.len()
+        } else {
+            self.insert_position(item.priority)
+        };
+        self.items.insert(position, item);
 
         // If this is the first item and nothing is playing, set it as current
         if self.current_index.is_none() && self.items.len() == 1 {
             self.current_index = Some(0);
         }
+
+        if self.fair_queue {
+            self.reorder_fair();
+        }
     }
 
-    pub fn can_user_add(&self, username: &str, user_limit: Option<usize>) -> bool {
-        // App User is not subject to limits
-        if username == "App User" {
-            return true;
+    // 현재 곡 이후의 대기열을 우선순위별로, 그 안에서는 신청자별로 한 곡씩 번갈아 정렬
+    // (고정된 항목은 자리를 유지하고 같은 신청자의 곡 순서는 그대로)
+    fn reorder_fair(&mut self) {
+        self.pinned
+            .retain(|id| self.items.iter().any(|item| &item.id == id));
+
+        let start = self.current_index.map_or(0, |current| current + 1);
+        if start >= self.items.len() {
+            return;
+        }
+
+        let (pinned_slots, mut unpinned): (Vec<_>, Vec<_>) = self
+            .items
+            .drain(start..)
+            .enumerate()
+            .partition(|(_, item)| self.pinned.contains(&item.id));
+
+        // 현재 곡의 신청자는 이번 차례를 이미 쓴 것으로 계산
+        let mut rounds: HashMap<(u8, String), usize> = HashMap::new();
+        if let Some(current) = self.current_item() {
+            rounds.insert((current.priority.rank(), requester_key(current)), 1);
         }
+        let mut keyed: Vec<_> = unpinned
+            .drain(..)
+            .map(|(position, item)| {
+                let rank = item.priority.rank();
+                let round = rounds.entry((rank, requester_key(&item))).or_insert(0);
+                let key = (std::cmp::Reverse(rank), *round, position);
+                *round += 1;
+                (key, item)
+            })
+            .collect();
+        keyed.sort_by_key(|(key, _)| *key);
+
+        let mut pinned_slots = pinned_slots.into_iter().peekable();
+        let mut ordered = keyed.into_iter().map(|(_, item)| item);
+        let total = self.items.len() + pinned_slots.len() + ordered.len();
+        while self.items.len() < total {
+            let position = self.items.len() - start;
+            let item = match pinned_slots.peek() {
+                Some((slot, _)) if *slot <= position => pinned_slots.next().map(|(_, item)| item),
+                _ => ordered
+                    .next()
+                    .or_else(|| pinned_slots.next().map(|(_, item)| item)),
+            };
+            self.items.extend(item);
+        }
+    }
 
+    // 제한을 적용하지 않을 신청(앱에서 직접 추가, 후원)은 호출하는 쪽에서 user_limit을 None으로 넘김
+    pub fn can_user_add(&self, username: &str, user_limit: Option<usize>) -> bool {
         // If no limit is set, allow
         let Some(limit) = user_limit else {
             return true;
         };
 
-        // Count user's songs after current playing index
-        let start_index = self.current_index.unwrap_or(0);
+        // Count only songs that have not started playing yet
         let user_song_count = self
-            .items
-            .iter()
-            .skip(start_index + 1) // Count only songs after currently playing
-            .filter(|item| item.added_by == username)
+            .upcoming()
+            .into_iter()
+            .filter(|item| item.source == ItemSource::Request && item.added_by == username)
             .count();
 
         user_song_count < limit
@@ -81,6 +325,64 @@ impl PlaylistState {
         Ok(())
     }
 
+    // 아직 재생되지 않은 신청곡과 대기 순번 (1부터)
+    pub fn user_queue(&self, user_id: &str) -> Vec<(usize, &PlaylistItem)> {
+        self.upcoming()
+            .into_iter()
+            .enumerate()
+            .filter(|(_, item)| item.requester_id.as_deref() == Some(user_id))
+            .map(|(i, item)| (i + 1, item))
+            .collect()
+    }
+
+    // 가장 최근에 신청한 대기 중인 곡을 취소하고 원래 위치와 함께 반환
+    pub fn remove_last_request(&mut self, user_id: &str) -> Option<(usize, PlaylistItem)> {
+        let id = self
+            .upcoming()
+            .into_iter()
+            .filter(|item| item.requester_id.as_deref() == Some(user_id))
+            .max_by_key(|item| item.added_at)?
+            .id
+            .clone();
+        let index = self.position_of(&id)?;
+        self.remove_item(index).map(|item| (index, item))
+    }
+
+    pub fn position_of(&self, id: &str) -> Option<usize> {
+        self.items.iter().position(|item| item.id == id)
+    }
+
+    // 지정한 위치에 곡을 다시 넣음 (현재 곡은 그대로 유지)
+    pub fn insert_item(&mut self, index: usize, item: PlaylistItem) {
+        let index = index.min(self.items.len());
+        self.items.insert(index, item);
+        match self.current_index {
+            Some(current) if index <= current => self.current_index = Some(current + 1),
+            None if self.items.len() == 1 => self.current_index = Some(0),
+            _ => {}
+        }
+    }
+
+    // 비운 목록을 되살림 (그 사이 추가된 곡은 뒤에 붙이고, 재생 중인 곡이 있으면 유지)
+    pub fn restore_cleared(
+        &mut self,
+        items: VecDeque<PlaylistItem>,
+        current_index: Option<usize>,
+        started_id: Option<String>,
+    ) {
+        let playing = self.current_index.filter(|_| self.current_started());
+        let restored_len = items.len();
+        let added = std::mem::replace(&mut self.items, items);
+        self.items.extend(added);
+        match playing {
+            Some(current) => self.current_index = Some(restored_len + current),
+            None => {
+                self.current_index = current_index;
+                self.started_id = started_id;
+            }
+        }
+    }
+
     pub fn remove_item(&mut self, index: usize) -> Option<PlaylistItem> {
         if index >= self.items.len() {
             return None;
@@ -118,6 +420,10 @@ impl PlaylistState {
 
         // Remove item from original position
         if let Some(item) = self.items.remove(from) {
+            // 공평 정렬 중에 직접 옮긴 곡은 자리를 유지
+            if self.fair_queue {
+                self.pinned.insert(item.id.clone());
+            }
             // Insert at new position
             self.items.insert(to, item);
 
@@ -139,32 +445,22 @@ impl PlaylistState {
     }
 
     pub fn next(&mut self) -> Option<&PlaylistItem> {
+        self.drop_fallback_current();
         if self.items.is_empty() {
             return None;
         }
 
-        match self.current_index {
-            Some(current) => {
-                if current + 1 < self.items.len() {
-                    self.current_index = Some(current + 1);
-                    self.items.get(current + 1)
-                } else if self.autoplay {
-                    // Loop back to beginning
-                    self.current_index = Some(0);
-                    self.items.get(0)
-                } else {
-                    None
-                }
-            }
-            None => {
-                if !self.items.is_empty() {
-                    self.current_index = Some(0);
-                    self.items.get(0)
-                } else {
-                    None
-                }
-            }
+        if self.mode == PlaybackMode::Shuffle {
+            return self.next_shuffled();
         }
+
+        let next_index = match self.current_index {
+            Some(current) if current + 1 < self.items.len() => current + 1,
+            // 마지막 곡에서는 반복 모드일 때만 처음으로
+            Some(_) if self.mode == PlaybackMode::NoRepeat => return None,
+            _ => 0,
+        };
+        self.switch_to(next_index)
     }
 
     pub fn previous(&mut self) -> Option<&PlaylistItem> {
@@ -172,29 +468,139 @@ impl PlaylistState {
             return None;
         }
 
-        match self.current_index {
-            Some(current) => {
-                if current > 0 {
-                    self.current_index = Some(current - 1);
-                    self.items.get(current - 1)
-                } else if self.autoplay {
-                    // Loop to end
-                    let last_index = self.items.len() - 1;
-                    self.current_index = Some(last_index);
-                    self.items.get(last_index)
-                } else {
-                    None
+        if self.mode == PlaybackMode::Shuffle {
+            return self.previous_shuffled();
+        }
+
+        let previous_index = match self.current_index? {
+            0 if self.mode == PlaybackMode::NoRepeat => return None,
+            // Loop to end
+            0 => self.items.len() - 1,
+            current => current - 1,
+        };
+        self.switch_to(previous_index)
+    }
+
+    // 곡 재생이 끝났을 때 다음 곡 (한 곡 반복이면 같은 곡을 다시 재생)
+    pub fn advance(&mut self) -> Option<&PlaylistItem> {
+        if self.mode == PlaybackMode::RepeatOne && !self.is_playing_fallback() {
+            if let Some(current) = self.current_index {
+                return self.switch_to(current);
+            }
+        }
+        self.next()
+    }
+
+    // 이번 회차에 아직 재생하지 않은 곡 중에서 무작위로 선택
+    fn next_shuffled(&mut self) -> Option<&PlaylistItem> {
+        let current_id = self.current_item().map(|item| item.id.clone());
+        let unplayed = |played: &[String]| -> Vec<usize> {
+            let played: HashSet<&str> = played.iter().map(String::as_str).collect();
+            (0..self.items.len())
+                .filter(|&i| !played.contains(self.items[i].id.as_str()))
+                .collect()
+        };
+
+        let mut candidates = unplayed(&self.shuffle_history[self.shuffle_cycle_start..]);
+        if candidates.is_empty() {
+            // 모든 곡을 한 번씩 재생했으면 새 회차 시작 (방금 재생한 곡은 바로 반복하지 않음)
+            self.shuffle_cycle_start = self.shuffle_history.len();
+            candidates = (0..self.items.len())
+                .filter(|&i| {
+                    self.items.len() == 1 || Some(&self.items[i].id) != current_id.as_ref()
+                })
+                .collect();
+        }
+
+        let index = *candidates.choose(&mut rand::thread_rng())?;
+        self.shuffle_history.push(self.items[index].id.clone());
+        self.trim_shuffle_history();
+        self.switch_to(index)
+    }
+
+    // 실제로 재생했던 순서를 거슬러 올라감 (목록에서 빠진 곡은 건너뜀)
+    fn previous_shuffled(&mut self) -> Option<&PlaylistItem> {
+        if self.shuffle_history.len() < 2 {
+            return None;
+        }
+
+        let current_id = self.shuffle_history.pop();
+        while let Some(id) = self.shuffle_history.last() {
+            if let Some(index) = self.items.iter().position(|item| &item.id == id) {
+                self.shuffle_cycle_start =
+                    self.shuffle_cycle_start.min(self.shuffle_history.len() - 1);
+                return self.switch_to(index);
+            }
+            self.shuffle_history.pop();
+        }
+
+        // 돌아갈 곡이 없으면 현재 곡 기록을 되돌림
+        self.shuffle_history.extend(current_id);
+        self.shuffle_cycle_start = 0;
+        None
+    }
+
+    pub fn is_playing_fallback(&self) -> bool {
+        self.current_item()
+            .is_some_and(|item| item.source == ItemSource::Fallback)
+    }
+
+    // 기본 재생목록 곡은 다음 곡으로 넘어가면 목록에서 빠짐 (반복 재생 대상이 아님)
+    fn drop_fallback_current(&mut self) {
+        if let Some(current) = self.current_index.filter(|_| self.is_playing_fallback()) {
+            self.items.remove(current);
+            self.current_index = current.checked_sub(1);
+        }
+    }
+
+    // 대기 중인 신청곡이 없을 때 기본 재생목록 곡을 끝에 붙여 재생
+    pub fn play_fallback(&mut self, item: PlaylistItem) -> Option<&PlaylistItem> {
+        self.drop_fallback_current();
+        self.items.push_back(item);
+        self.is_playing = true;
+        self.switch_to(self.items.len() - 1)
+    }
+
+    // 현재 곡을 바꾸고 재생 기록을 남김 (설정에 따라 끝난 곡은 목록에서 제거)
+    fn switch_to(&mut self, mut index: usize) -> Option<&PlaylistItem> {
+        if (self.remove_finished && self.current_started()) || self.is_playing_fallback() {
+            if let Some(current) = self.current_index.filter(|&current| current != index) {
+                self.items.remove(current);
+                if current < index {
+                    index -= 1;
                 }
             }
-            None => None,
+        }
+
+        let item = self.items.get(index)?.clone();
+        self.current_index = Some(index);
+        self.started_id = Some(item.id.clone());
+        self.history.push_back(PlayedItem {
+            item,
+            played_at: chrono::Utc::now().timestamp(),
+        });
+        if self.history.len() > PLAY_HISTORY_LIMIT {
+            self.history.pop_front();
+        }
+        self.items.get(index)
+    }
+
+    fn trim_shuffle_history(&mut self) {
+        if self.shuffle_history.len() > SHUFFLE_HISTORY_LIMIT {
+            let excess = self.shuffle_history.len() - SHUFFLE_HISTORY_LIMIT;
+            self.shuffle_history.drain(..excess);
+            self.shuffle_cycle_start = self.shuffle_cycle_start.saturating_sub(excess);
         }
     }
 
     pub fn play_at(&mut self, index: usize) -> Option<&PlaylistItem> {
         if index < self.items.len() {
-            self.current_index = Some(index);
             self.is_playing = true;
-            self.items.get(index)
+            if self.mode == PlaybackMode::Shuffle {
+                self.shuffle_history.push(self.items[index].id.clone());
+                self.trim_shuffle_history();
+            }
+            self.switch_to(index)
         } else {
             None
         }
@@ -204,53 +610,128 @@ impl PlaylistState {
         self.items.clear();
         self.current_index = None;
         self.is_playing = false;
+        self.shuffle_history.clear();
+        self.shuffle_cycle_start = 0;
+        self.pinned.clear();
+        self.started_id = None;
     }
 
     pub fn set_autoplay(&mut self, enabled: bool) {
         self.autoplay = enabled;
     }
+
+    pub fn set_fair_queue(&mut self, enabled: bool) {
+        self.fair_queue = enabled;
+        if enabled {
+            self.reorder_fair();
+        } else {
+            // 다시 켰을 때 예전에 옮긴 곡이 고정되어 있지 않도록 함
+            self.pinned.clear();
+        }
+    }
+
+    pub fn set_remove_finished(&mut self, enabled: bool) {
+        self.remove_finished = enabled;
+    }
+
+    pub fn clear_history(&mut self) {
+        self.history.clear();
+    }
+
+    pub fn set_mode(&mut self, mode: PlaybackMode) {
+        self.mode = mode;
+        // 셔플 기록은 현재 곡부터 새로 시작
+        self.shuffle_history = self
+            .current_item()
+            .map(|item| vec![item.id.clone()])
+            .unwrap_or_default();
+        self.shuffle_cycle_start = 0;
+    }
+
+    // 저장된 파일에서 불러온 상태 정리 (재생은 멈춘 상태로 시작)
+    pub fn validate_restored(&mut self) {
+        self.is_playing = false;
+        self.current_index = match self.current_index {
+            _ if self.items.is_empty() => None,
+            Some(index) if index >= self.items.len() => Some(self.items.len() - 1),
+            index => index,
+        };
+        self.shuffle_cycle_start = self.shuffle_cycle_start.min(self.shuffle_history.len());
+        // 재생을 시작한 곡 ID가 없던 이전 저장 파일은 마지막 재생 기록으로 판단
+        if self.started_id.is_none() {
+            self.started_id = self.history.back().map(|played| played.item.id.clone());
+        }
+    }
+}
+
+// 공평 정렬에서 같은 신청자로 볼 기준 (uid가 없는 곡은 닉네임)
+fn requester_key(item: &PlaylistItem) -> String {
+    item.requester_id
+        .clone()
+        .unwrap_or_else(|| item.added_by.clone())
+}
+
+// "3:45", "1:02:03" 형식의 재생 시간을 초로 변환
+pub fn parse_duration_secs(duration: &str) -> Option<u64> {
+    let parts = duration
+        .trim()
+        .split(':')
+        .map(|part| part.parse::<u64>().ok())
+        .collect::<Option<Vec<_>>>()?;
+    if parts.is_empty() || parts.len() > 3 {
+        return None;
+    }
+    Some(parts.iter().fold(0, |total, part| total * 60 + part))
+}
+
+pub fn format_duration_secs(secs: u64) -> String {
+    let (hours, minutes, seconds) = (secs / 3600, secs % 3600 / 60, secs % 60);
+    if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, minutes, seconds)
+    } else {
+        format!("{}:{:02}", minutes, seconds)
+    }
 }
 
 // YouTube URL detection and extraction
 pub fn is_youtube_url(text: &str) -> bool {
-    text.contains("youtube.com/watch?v=")
-        || text.contains("youtu.be/")
-        || text.contains("youtube.com/shorts/")
-        || text.contains("music.youtube.com/watch?v=")
+    link::parse_youtube_url(text).is_some()
 }
 
+// 링크 또는 영상 ID에서 영상 ID
 pub fn extract_youtube_id(url: &str) -> Option<String> {
-    // Handle youtube.com/watch?v=VIDEO_ID format
-    if let Some(start) = url.find("watch?v=") {
-        let id_start = start + 8;
-        let id_end = url[id_start..]
-            .find(|c: char| !c.is_alphanumeric() && c != '-' && c != '_')
-            .map(|i| id_start + i)
-            .unwrap_or(url.len());
-        return Some(url[id_start..id_end].to_string());
-    }
-
-    // Handle youtu.be/VIDEO_ID format
-    if let Some(start) = url.find("youtu.be/") {
-        let id_start = start + 9;
-        let id_end = url[id_start..]
-            .find(|c: char| !c.is_alphanumeric() && c != '-' && c != '_')
-            .map(|i| id_start + i)
-            .unwrap_or(url.len());
-        return Some(url[id_start..id_end].to_string());
-    }
-
-    // Handle youtube.com/shorts/VIDEO_ID format
-    if let Some(start) = url.find("shorts/") {
-        let id_start = start + 7;
-        let id_end = url[id_start..]
-            .find(|c: char| !c.is_alphanumeric() && c != '-' && c != '_')
-            .map(|i| id_start + i)
-            .unwrap_or(url.len());
-        return Some(url[id_start..id_end].to_string());
-    }
-
-    None
+    link::parse_youtube_ref(url)?.video_id
+}
+
+// 테스트에서 같이 쓰는 플레이리스트 항목
+#[cfg(test)]
+pub(crate) fn test_item(id: &str) -> PlaylistItem {
+    PlaylistItem {
+        id: id.to_string(),
+        video_id: id.to_string(),
+        title: format!("Song {}", id),
+        channel: "Channel".to_string(),
+        duration: None,
+        thumbnail: None,
+        url: format!("https://youtu.be/{}", id),
+        added_by: "viewer".to_string(),
+        requester_id: None,
+        added_at: 0,
+        priority: RequestPriority::Normal,
+        source: ItemSource::Request,
+        metadata: None,
+        start_seconds: None,
+    }
+}
+
+// 테스트에서 같이 쓰는 플레이리스트 (ids 순서대로 추가)
+#[cfg(test)]
+pub(crate) fn test_playlist(ids: &[&str]) -> PlaylistState {
+    let mut playlist = PlaylistState::new();
+    for id in ids {
+        playlist.add_item(test_item(id));
+    }
+    playlist
 }
 
 #[cfg(test)]
@@ -268,6 +749,381 @@ mod tests {
         assert!(!is_youtube_url("https://google.com"));
     }
 
+    fn current_id(playlist: &PlaylistState) -> String {
+        playlist.current_item().unwrap().id.clone()
+    }
+
+    #[test]
+    fn test_repeat_modes() {
+        let mut playlist = test_playlist(&["a", "b"]);
+        assert_eq!(playlist.next().unwrap().id, "b");
+        assert!(playlist.next().is_none());
+        assert_eq!(playlist.previous().unwrap().id, "a");
+        assert!(playlist.previous().is_none());
+
+        playlist.set_mode(PlaybackMode::RepeatAll);
+        assert_eq!(playlist.previous().unwrap().id, "b");
+        assert_eq!(playlist.next().unwrap().id, "a");
+
+        // 한 곡 반복은 곡이 끝났을 때만 같은 곡, 건너뛰기는 다음 곡
+        playlist.set_mode(PlaybackMode::RepeatOne);
+        assert_eq!(playlist.advance().unwrap().id, "a");
+        assert_eq!(playlist.next().unwrap().id, "b");
+        assert_eq!(playlist.advance().unwrap().id, "b");
+    }
+
+    #[test]
+    fn test_shuffle_plays_every_item_once() {
+        let ids = ["a", "b", "c", "d", "e"];
+        let mut playlist = test_playlist(&ids);
+        playlist.set_mode(PlaybackMode::Shuffle);
+
+        let mut played = vec![current_id(&playlist)];
+        for _ in 1..ids.len() {
+            played.push(playlist.next().unwrap().id.clone());
+        }
+        let unique: HashSet<_> = played.iter().collect();
+        assert_eq!(unique.len(), ids.len());
+
+        // 다음 회차에서도 방금 재생한 곡은 바로 반복하지 않음
+        let last = played.last().unwrap().clone();
+        assert_ne!(playlist.next().unwrap().id, last);
+    }
+
+    #[test]
+    fn test_shuffle_previous_follows_play_order() {
+        let mut playlist = test_playlist(&["a", "b", "c", "d"]);
+        playlist.set_mode(PlaybackMode::Shuffle);
+
+        let mut played = vec![current_id(&playlist)];
+        for _ in 0..3 {
+            played.push(playlist.next().unwrap().id.clone());
+        }
+
+        for expected in played.iter().rev().skip(1) {
+            assert_eq!(&playlist.previous().unwrap().id, expected);
+        }
+        assert!(playlist.previous().is_none());
+        assert_eq!(current_id(&playlist), played[0]);
+    }
+
+    #[test]
+    fn test_shuffle_previous_skips_removed_items() {
+        let mut playlist = test_playlist(&["a", "b", "c"]);
+        playlist.set_mode(PlaybackMode::Shuffle);
+
+        let first = current_id(&playlist);
+        let second = playlist.next().unwrap().id.clone();
+        playlist.next();
+
+        let removed = playlist.items.iter().position(|i| i.id == second).unwrap();
+        playlist.remove_item(removed);
+        assert_eq!(playlist.previous().unwrap().id, first);
+    }
+
+    #[test]
+    fn test_priority_insert() {
+        let mut playlist = test_playlist(&["a", "b"]);
+        playlist.play_at(0);
+
+        let mut donation = test_item("d");
+        donation.priority = RequestPriority::Donation { amount: 5000 };
+        playlist.add_item(donation);
+        let mut subscriber = test_item("s");
+        subscriber.priority = RequestPriority::Subscriber;
+        playlist.add_item(subscriber);
+        let mut donation = test_item("d2");
+        donation.priority = RequestPriority::Donation { amount: 1000 };
+        playlist.add_item(donation);
+
+        let order: Vec<_> = playlist.items.iter().map(|i| i.id.as_str()).collect();
+        assert_eq!(order, ["a", "d", "d2", "s", "b"]);
+        assert_eq!(playlist.current_index, Some(0));
+    }
+
+    #[test]
+    fn test_priority_insert_before_unplayed_current() {
+        let mut playlist = test_playlist(&["a"]);
+
+        let mut donation = test_item("d");
+        donation.priority = RequestPriority::Donation { amount: 1000 };
+        playlist.add_item(donation);
+
+        // 아직 재생하지 않은 현재 곡보다 먼저 재생
+        assert_eq!(current_id(&playlist), "d");
+    }
+
+    fn requested(id: &str, user: &str) -> PlaylistItem {
+        PlaylistItem {
+            added_by: user.to_string(),
+            requester_id: Some(format!("uid-{}", user)),
+            ..test_item(id)
+        }
+    }
+
+    fn order(playlist: &PlaylistState) -> Vec<&str> {
+        playlist.items.iter().map(|i| i.id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_fair_queue() {
+        let mut playlist = PlaylistState::new();
+        playlist.set_fair_queue(true);
+        for (id, user) in [
+            ("a1", "a"),
+            ("a2", "a"),
+            ("a3", "a"),
+            ("b1", "b"),
+            ("c1", "c"),
+        ] {
+            playlist.add_item(requested(id, user));
+        }
+        // 현재 곡은 그대로, 이후는 신청자별로 한 곡씩
+        assert_eq!(order(&playlist), ["a1", "b1", "c1", "a2", "a3"]);
+
+        playlist.add_item(requested("b2", "b"));
+        assert_eq!(order(&playlist), ["a1", "b1", "c1", "a2", "b2", "a3"]);
+    }
+
+    #[test]
+    fn test_fair_queue_by_requester_id() {
+        let mut playlist = PlaylistState::new();
+        playlist.set_fair_queue(true);
+        // 닉네임이 같아도 uid가 다르면 다른 신청자
+        for (id, uid) in [("x1", "uid-x"), ("x2", "uid-x"), ("y1", "uid-y")] {
+            playlist.add_item(PlaylistItem {
+                requester_id: Some(uid.to_string()),
+                ..requested(id, "same")
+            });
+        }
+        assert_eq!(order(&playlist), ["x1", "y1", "x2"]);
+    }
+
+    #[test]
+    fn test_fair_queue_keeps_pins_and_priority() {
+        let mut playlist = PlaylistState::new();
+        for (id, user) in [("a1", "a"), ("a2", "a"), ("a3", "a"), ("b1", "b")] {
+            playlist.add_item(requested(id, user));
+        }
+        playlist.play_at(0);
+        // 공평 정렬을 켜기 전에 옮긴 곡은 고정되지 않음
+        playlist.move_item(2, 1).unwrap();
+        assert!(playlist.pinned.is_empty());
+        playlist.set_fair_queue(true);
+        assert_eq!(order(&playlist), ["a1", "b1", "a3", "a2"]);
+
+        // a3을 바로 다음 자리에 고정
+        playlist.move_item(2, 1).unwrap();
+        playlist.add_item(requested("b2", "b"));
+        assert_eq!(order(&playlist), ["a1", "a3", "b1", "a2", "b2"]);
+
+        let mut donation = requested("c1", "c");
+        donation.priority = RequestPriority::Donation { amount: 1000 };
+        playlist.add_item(donation);
+        assert_eq!(order(&playlist), ["a1", "a3", "c1", "b1", "a2", "b2"]);
+        assert_eq!(playlist.current_index, Some(0));
+
+        playlist.set_fair_queue(false);
+        assert!(playlist.pinned.is_empty());
+    }
+
+    #[test]
+    fn test_user_queue() {
+        let mut playlist = PlaylistState::new();
+        for (id, user) in [("a1", "a"), ("b1", "b"), ("a2", "a"), ("b2", "b")] {
+            playlist.add_item(requested(id, user));
+        }
+        playlist.play_at(0);
+
+        let queue: Vec<_> = playlist
+            .user_queue("uid-b")
+            .into_iter()
+            .map(|(position, item)| (position, item.id.as_str()))
+            .collect();
+        assert_eq!(queue, [(1, "b1"), (3, "b2")]);
+        // 재생 중인 곡은 대기열에 포함되지 않음
+        assert_eq!(playlist.user_queue("uid-a").len(), 1);
+
+        // 닉네임이 같아도 다른 사람의 신청곡은 보이지 않음
+        playlist.add_item(PlaylistItem {
+            requester_id: Some("uid-other".to_string()),
+            ..requested("b3", "b")
+        });
+        assert_eq!(playlist.user_queue("uid-b").len(), 2);
+        // 앱에서 추가한 곡은 누구의 신청곡도 아님
+        playlist.add_item(PlaylistItem {
+            added_by: "b".to_string(),
+            ..test_item("b4")
+        });
+        assert_eq!(playlist.user_queue("b").len(), 0);
+    }
+
+    #[test]
+    fn test_remove_last_request() {
+        let mut playlist = PlaylistState::new();
+        for (i, (id, user)) in [("a1", "a"), ("a2", "a"), ("b1", "b"), ("a3", "a")]
+            .into_iter()
+            .enumerate()
+        {
+            playlist.add_item(PlaylistItem {
+                added_at: i as i64,
+                ..requested(id, user)
+            });
+        }
+        playlist.play_at(0);
+
+        assert_eq!(playlist.remove_last_request("uid-a").unwrap().1.id, "a3");
+        assert_eq!(
+            playlist
+                .remove_last_request("uid-a")
+                .map(|(index, item)| (index, item.id)),
+            Some((1, "a2".to_string()))
+        );
+        // 재생 중인 곡은 취소할 수 없음
+        assert!(playlist.remove_last_request("uid-a").is_none());
+        assert_eq!(order(&playlist), ["a1", "b1"]);
+        assert_eq!(current_id(&playlist), "a1");
+    }
+
+    fn fallback(id: &str) -> PlaylistItem {
+        PlaylistItem {
+            source: ItemSource::Fallback,
+            ..test_item(id)
+        }
+    }
+
+    #[test]
+    fn test_fallback_yields_to_requests() {
+        let mut playlist = test_playlist(&["a"]);
+        playlist.play_at(0);
+        assert!(playlist.advance().is_none());
+
+        playlist.play_fallback(fallback("f1"));
+        assert!(playlist.is_playing_fallback());
+        // 기본 재생목록 곡끼리는 서로 교체되고 목록에 쌓이지 않음
+        playlist.play_fallback(fallback("f2"));
+        assert_eq!(order(&playlist), ["a", "f2"]);
+
+        playlist.add_item(requested("b", "viewer"));
+        assert!(playlist.can_user_add("viewer", Some(2)));
+        assert_eq!(playlist.next().unwrap().id, "b");
+        assert_eq!(order(&playlist), ["a", "b"]);
+        assert!(!playlist.is_playing_fallback());
+    }
+
+    #[test]
+    fn test_fallback_is_not_repeated() {
+        let mut playlist = PlaylistState::new();
+        playlist.set_mode(PlaybackMode::RepeatOne);
+        playlist.play_fallback(fallback("f1"));
+
+        // 반복 모드여도 기본 재생목록 곡은 다시 재생하지 않음
+        assert!(playlist.advance().is_none());
+        assert!(playlist.items.is_empty());
+        assert_eq!(playlist.current_index, None);
+    }
+
+    #[test]
+    fn test_play_history() {
+        let mut playlist = test_playlist(&["a", "b", "c"]);
+        assert!(playlist.last_played().is_none());
+
+        playlist.play_at(0);
+        // 재생 중인 곡은 지난 곡이 아님
+        assert!(playlist.last_played().is_none());
+        playlist.next();
+        playlist.next();
+
+        let recent: Vec<_> = playlist
+            .recent_history(2)
+            .into_iter()
+            .map(|played| played.item.id)
+            .collect();
+        assert_eq!(recent, ["c", "b"]);
+        assert_eq!(playlist.last_played().unwrap().item.id, "b");
+    }
+
+    #[test]
+    fn test_clear_history_while_playing() {
+        let mut playlist = test_playlist(&["a", "b", "c"]);
+        playlist.play_at(0);
+        playlist.clear_history();
+
+        // 재생 기록을 지워도 현재 곡은 재생 중인 곡으로 취급
+        let upcoming: Vec<_> = playlist.upcoming().iter().map(|i| i.id.as_str()).collect();
+        assert_eq!(upcoming, ["b", "c"]);
+        assert!(playlist.last_played().is_none());
+
+        let mut donation = test_item("d");
+        donation.priority = RequestPriority::Donation { amount: 1000 };
+        playlist.add_item(donation);
+        assert_eq!(order(&playlist), ["a", "d", "b", "c"]);
+
+        playlist.set_remove_finished(true);
+        playlist.next();
+        assert_eq!(order(&playlist), ["d", "b", "c"]);
+    }
+
+    #[test]
+    fn test_remove_finished() {
+        let mut playlist = test_playlist(&["a", "b", "c"]);
+        playlist.set_remove_finished(true);
+
+        // 재생을 시작하지 않은 곡은 건너뛰어도 남겨둠
+        playlist.next();
+        assert_eq!(playlist.items.len(), 3);
+
+        playlist.next();
+        assert_eq!(playlist.items.len(), 2);
+        assert_eq!(current_id(&playlist), "c");
+        assert_eq!(playlist.current_index, Some(1));
+        assert_eq!(playlist.last_played().unwrap().item.id, "b");
+    }
+
+    #[test]
+    fn test_user_limit_counts_upcoming_songs() {
+        let mut playlist = test_playlist(&["a", "b"]);
+        // 아직 재생하지 않은 첫 곡도 대기열에 포함
+        assert!(!playlist.can_user_add("viewer", Some(2)));
+
+        playlist.play_at(0);
+        assert!(playlist.can_user_add("viewer", Some(2)));
+        assert!(!playlist.can_user_add("viewer", Some(1)));
+        assert!(playlist.can_user_add("viewer", None));
+        // 닉네임이 App User여도 제한은 그대로 적용
+        let mut playlist = PlaylistState::new();
+        playlist.add_item(PlaylistItem {
+            added_by: "App User".to_string(),
+            ..test_item("x")
+        });
+        assert!(!playlist.can_user_add("App User", Some(1)));
+    }
+
+    #[test]
+    fn test_validate_restored() {
+        let mut playlist: PlaylistState = serde_json::from_str(
+            r#"{"items": [], "current_index": 3, "is_playing": true, "autoplay": true}"#,
+        )
+        .unwrap();
+        playlist.validate_restored();
+        assert_eq!(playlist.current_index, None);
+        assert!(!playlist.is_playing);
+
+        playlist.add_item(test_item("1"));
+        playlist.current_index = Some(5);
+        playlist.validate_restored();
+        assert_eq!(playlist.current_index, Some(0));
+    }
+
+    #[test]
+    fn test_parse_duration_secs() {
+        assert_eq!(parse_duration_secs("3:45"), Some(225));
+        assert_eq!(parse_duration_secs("1:02:03"), Some(3723));
+        assert_eq!(parse_duration_secs("42"), Some(42));
+        assert_eq!(parse_duration_secs("LIVE"), None);
+        assert_eq!(parse_duration_secs(""), None);
+    }
+
     #[test]
     fn test_youtube_id_extraction() {
         assert_eq!(
@@ -287,4 +1143,18 @@ mod tests {
             Some("dQw4w9WgXcQ".to_string())
         );
     }
+
+    #[test]
+    fn test_playlist_id_extraction() {
+        let extract_playlist_id = |url| link::parse_youtube_url(url)?.playlist_id;
+        let url = "https://www.youtube.com/playlist?list=PLabc_123-x";
+        assert!(is_youtube_url(url));
+        assert_eq!(extract_youtube_id(url), None);
+        assert_eq!(extract_playlist_id(url), Some("PLabc_123-x".to_string()));
+        assert_eq!(
+            extract_playlist_id("https://www.youtube.com/watch?v=dQw4w9WgXcQ&list=RDabc&index=2"),
+            Some("RDabc".to_string())
+        );
+        assert_eq!(extract_playlist_id("https://youtu.be/dQw4w9WgXcQ"), None);
+    }
 }
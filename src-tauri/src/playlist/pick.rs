@@ -0,0 +1,175 @@
+use super::{format_duration_secs, parse_duration_secs, RequestPriority};
+use crate::youtube::YouTubeVideo;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+// 검색 결과를 보여 주고 신청자가 번호를 고르기를 기다리는 신청곡
+#[derive(Debug, Clone)]
+struct PendingPick {
+    videos: Vec<YouTubeVideo>,
+    priority: RequestPriority,
+    expires_at: Instant,
+}
+
+// 사용자별로 고르기를 기다리는 검색 결과 (새로 검색하면 이전 결과는 버림)
+#[derive(Debug, Default)]
+pub struct SearchPicks {
+    pending: HashMap<String, PendingPick>,
+}
+
+impl SearchPicks {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // 고를 결과가 없으면 기다리지 않고 거절
+    pub fn start(
+        &mut self,
+        user_id: &str,
+        videos: Vec<YouTubeVideo>,
+        priority: RequestPriority,
+        timeout: Duration,
+    ) -> Result<(), String> {
+        if videos.is_empty() {
+            return Err("No search results found".to_string());
+        }
+
+        let now = Instant::now();
+        self.pending.retain(|_, pick| pick.expires_at > now);
+        self.pending.insert(
+            user_id.to_string(),
+            PendingPick {
+                videos,
+                priority,
+                expires_at: now + timeout,
+            },
+        );
+        Ok(())
+    }
+
+    // 번호가 범위를 벗어나면 다시 고를 수 있도록 남겨 둠
+    pub fn pick(
+        &mut self,
+        user_id: &str,
+        choice: usize,
+    ) -> Result<(YouTubeVideo, RequestPriority), String> {
+        let mut pending = self
+            .pending
+            .remove(user_id)
+            .ok_or_else(|| "고를 검색 결과가 없습니다. 먼저 곡을 신청해 주세요".to_string())?;
+
+        if pending.expires_at <= Instant::now() {
+            return Err("선택 시간이 지났습니다. 다시 신청해 주세요".to_string());
+        }
+        if choice == 0 || choice > pending.videos.len() {
+            let message = format!("1부터 {}까지의 번호를 골라 주세요", pending.videos.len());
+            self.pending.insert(user_id.to_string(), pending);
+            return Err(message);
+        }
+
+        Ok((pending.videos.swap_remove(choice - 1), pending.priority))
+    }
+}
+
+// 채팅으로 보낼 검색 결과 목록 (1. 제목 [3:45] / 2. ...)
+pub fn describe_choices(videos: &[YouTubeVideo]) -> String {
+    videos
+        .iter()
+        .enumerate()
+        .map(
+            |(i, video)| match video.duration.as_deref().and_then(parse_duration_secs) {
+                Some(secs) => format!(
+                    "{}. {} [{}]",
+                    i + 1,
+                    video.title,
+                    format_duration_secs(secs)
+                ),
+                None => format!("{}. {}", i + 1, video.title),
+            },
+        )
+        .collect::<Vec<_>>()
+        .join(" / ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::youtube::test_video;
+
+    fn video(id: &str, duration: Option<&str>) -> YouTubeVideo {
+        YouTubeVideo {
+            duration: duration.map(str::to_string),
+            ..test_video(id)
+        }
+    }
+
+    #[test]
+    fn test_pick_from_results() {
+        let mut picks = SearchPicks::new();
+        assert!(picks.pick("viewer", 1).is_err());
+
+        picks
+            .start(
+                "viewer",
+                vec![video("a", Some("3:45")), video("b", None), video("c", None)],
+                RequestPriority::Subscriber,
+                Duration::from_secs(60),
+            )
+            .unwrap();
+        // 다른 사용자의 결과는 고를 수 없음
+        assert!(picks.pick("other", 1).is_err());
+        // 범위를 벗어난 번호는 다시 고를 수 있음
+        assert_eq!(
+            picks.pick("viewer", 4).unwrap_err(),
+            "1부터 3까지의 번호를 골라 주세요"
+        );
+
+        let (picked, priority) = picks.pick("viewer", 2).unwrap();
+        assert_eq!(picked.video_id, "b");
+        assert_eq!(priority, RequestPriority::Subscriber);
+        // 한 번 고르면 끝
+        assert!(picks.pick("viewer", 1).is_err());
+    }
+
+    #[test]
+    fn test_pick_expires() {
+        let mut picks = SearchPicks::new();
+        picks
+            .start(
+                "viewer",
+                vec![video("a", None)],
+                RequestPriority::Normal,
+                Duration::ZERO,
+            )
+            .unwrap();
+        assert_eq!(
+            picks.pick("viewer", 1).unwrap_err(),
+            "선택 시간이 지났습니다. 다시 신청해 주세요"
+        );
+    }
+
+    #[test]
+    fn test_pick_without_results() {
+        let mut picks = SearchPicks::new();
+        assert_eq!(
+            picks
+                .start(
+                    "viewer",
+                    Vec::new(),
+                    RequestPriority::Normal,
+                    Duration::from_secs(60),
+                )
+                .unwrap_err(),
+            "No search results found"
+        );
+        assert!(picks.pick("viewer", 1).is_err());
+    }
+
+    #[test]
+    fn test_describe_choices() {
+        assert_eq!(
+            describe_choices(&[video("a", Some("03:45")), video("b", None)]),
+            "1. Song a [3:45] / 2. Song b"
+        );
+    }
+}
@@ -0,0 +1,354 @@
+use super::{format_duration_secs, parse_duration_secs, ItemSource, PlaylistItem, PlaylistState};
+use crate::commands::PlaylistLimits;
+
+// 신청곡 규칙에 걸린 이유
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RequestRejection {
+    TooLong { duration_secs: u64, max_secs: u64 },
+    TooShort { duration_secs: u64, min_secs: u64 },
+    AlreadyQueued,
+    RecentlyPlayed { minutes_ago: i64 },
+    BlockedVideo,
+    BlockedChannel { channel: String },
+    BlockedKeyword { keyword: String },
+    LiveStream,
+    Upcoming,
+    NotEmbeddable,
+    AgeRestricted,
+    RegionBlocked { region: String },
+    Unavailable { reason: String },
+}
+
+impl RequestRejection {
+    pub fn message(&self, title: &str) -> String {
+        match self {
+            Self::TooLong {
+                duration_secs,
+                max_secs,
+            } => format!(
+                "'{}'은(는) 너무 깁니다 ({} / 최대 {})",
+                title,
+                format_duration_secs(*duration_secs),
+                format_duration_secs(*max_secs)
+            ),
+            Self::TooShort {
+                duration_secs,
+                min_secs,
+            } => format!(
+                "'{}'은(는) 너무 짧습니다 ({} / 최소 {})",
+                title,
+                format_duration_secs(*duration_secs),
+                format_duration_secs(*min_secs)
+            ),
+            Self::AlreadyQueued => format!("'{}'은(는) 이미 대기열에 있습니다", title),
+            Self::RecentlyPlayed { minutes_ago } => {
+                format!("'{}'은(는) {}분 전에 재생된 곡입니다", title, minutes_ago)
+            }
+            Self::BlockedVideo => format!("'{}'은(는) 신청할 수 없는 영상입니다", title),
+            Self::BlockedChannel { channel } => {
+                format!("'{}' 채널의 영상은 신청할 수 없습니다", channel)
+            }
+            Self::BlockedKeyword { keyword } => {
+                format!("제목에 금지어 '{}'이(가) 포함되어 있습니다", keyword)
+            }
+            Self::LiveStream => format!("'{}'은(는) 라이브 방송이라 신청할 수 없습니다", title),
+            Self::Upcoming => format!("'{}'은(는) 아직 시작하지 않은 방송입니다", title),
+            Self::NotEmbeddable => {
+                format!("'{}'은(는) 외부 재생이 허용되지 않은 영상입니다", title)
+            }
+            Self::AgeRestricted => format!("'{}'은(는) 연령 제한 영상입니다", title),
+            Self::RegionBlocked { region } => {
+                format!(
+                    "'{}'은(는) {} 지역에서 볼 수 없는 영상입니다",
+                    title, region
+                )
+            }
+            Self::Unavailable { reason } => {
+                format!("'{}'은(는) 재생할 수 없습니다 ({})", title, reason)
+            }
+        }
+    }
+}
+
+fn contains_ignore_case(list: &[String], value: &str) -> bool {
+    list.iter()
+        .any(|entry| entry.trim().eq_ignore_ascii_case(value.trim()))
+}
+
+// 방송 지역(region)의 플레이어에서 재생할 수 없는 영상인지 확인 (정보가 없는 곡은 통과)
+pub fn check_playable(item: &PlaylistItem, region: &str) -> Result<(), RequestRejection> {
+    let Some(metadata) = &item.metadata else {
+        return Ok(());
+    };
+
+    if metadata.is_blocked_in(region) {
+        return Err(RequestRejection::RegionBlocked {
+            region: region.to_string(),
+        });
+    }
+    if metadata.age_restricted {
+        return Err(RequestRejection::AgeRestricted);
+    }
+    if let Some(reason) = &metadata.unavailable_reason {
+        return Err(RequestRejection::Unavailable {
+            reason: reason.clone(),
+        });
+    }
+    if metadata.is_upcoming {
+        return Err(RequestRejection::Upcoming);
+    }
+    if !metadata.embeddable {
+        return Err(RequestRejection::NotEmbeddable);
+    }
+    Ok(())
+}
+
+// 신청곡이 규칙에 맞는지 확인 (길이를 알 수 없는 영상은 길이 제한을 건너뜀)
+pub fn check_request(
+    limits: &PlaylistLimits,
+    playlist: &PlaylistState,
+    item: &PlaylistItem,
+    now: i64,
+) -> Result<(), RequestRejection> {
+    if contains_ignore_case(&limits.blocked_video_ids, &item.video_id) {
+        return Err(RequestRejection::BlockedVideo);
+    }
+
+    if contains_ignore_case(&limits.blocked_channels, &item.channel) {
+        return Err(RequestRejection::BlockedChannel {
+            channel: item.channel.clone(),
+        });
+    }
+
+    let title = item.title.to_lowercase();
+    if let Some(keyword) = limits
+        .blocked_keywords
+        .iter()
+        .map(|keyword| keyword.trim())
+        .find(|keyword| !keyword.is_empty() && title.contains(&keyword.to_lowercase()))
+    {
+        return Err(RequestRejection::BlockedKeyword {
+            keyword: keyword.to_string(),
+        });
+    }
+
+    // 길이 제한이 있으면 끝나지 않는 라이브 방송은 받지 않음
+    let is_live = item
+        .metadata
+        .as_ref()
+        .is_some_and(|metadata| metadata.is_live);
+    if is_live && limits.max_duration_secs.is_some() {
+        return Err(RequestRejection::LiveStream);
+    }
+
+    if let Some(duration_secs) = item.duration.as_deref().and_then(parse_duration_secs) {
+        if let Some(max_secs) = limits.max_duration_secs.filter(|&max| duration_secs > max) {
+            return Err(RequestRejection::TooLong {
+                duration_secs,
+                max_secs,
+            });
+        }
+        if let Some(min_secs) = limits.min_duration_secs.filter(|&min| duration_secs < min) {
+            return Err(RequestRejection::TooShort {
+                duration_secs,
+                min_secs,
+            });
+        }
+    }
+
+    if limits.prevent_duplicates {
+        if playlist
+            .upcoming()
+            .iter()
+            .chain(playlist.current_item().as_ref())
+            .any(|queued| queued.source == ItemSource::Request && queued.video_id == item.video_id)
+        {
+            return Err(RequestRejection::AlreadyQueued);
+        }
+
+        let window_secs = limits.duplicate_window_mins as i64 * 60;
+        if let Some(played) = playlist.history.iter().rev().find(|played| {
+            played.item.source == ItemSource::Request
+                && played.item.video_id == item.video_id
+                && now - played.played_at < window_secs
+        }) {
+            return Err(RequestRejection::RecentlyPlayed {
+                minutes_ago: (now - played.played_at) / 60,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::playlist::{test_item, PlayedItem};
+    use crate::youtube::VideoMetadata;
+
+    fn item(video_id: &str, duration: Option<&str>) -> PlaylistItem {
+        PlaylistItem {
+            duration: duration.map(str::to_string),
+            ..test_item(video_id)
+        }
+    }
+
+    #[test]
+    fn test_duration_rules() {
+        let limits = PlaylistLimits {
+            max_duration_secs: Some(600),
+            min_duration_secs: Some(60),
+            ..PlaylistLimits::default()
+        };
+        let playlist = PlaylistState::new();
+
+        assert_eq!(
+            check_request(&limits, &playlist, &item("a", Some("10:00:00")), 0),
+            Err(RequestRejection::TooLong {
+                duration_secs: 36000,
+                max_secs: 600
+            })
+        );
+        assert!(matches!(
+            check_request(&limits, &playlist, &item("a", Some("0:30")), 0),
+            Err(RequestRejection::TooShort { .. })
+        ));
+        assert!(check_request(&limits, &playlist, &item("a", Some("3:45")), 0).is_ok());
+        assert!(check_request(&limits, &playlist, &item("a", None), 0).is_ok());
+    }
+
+    #[test]
+    fn test_blocklists() {
+        let limits = PlaylistLimits {
+            blocked_video_ids: vec!["banned".to_string()],
+            blocked_channels: vec!["bad channel".to_string()],
+            blocked_keywords: vec!["10시간".to_string()],
+            ..PlaylistLimits::default()
+        };
+        let playlist = PlaylistState::new();
+
+        assert_eq!(
+            check_request(&limits, &playlist, &item("banned", None), 0),
+            Err(RequestRejection::BlockedVideo)
+        );
+
+        let mut song = item("a", None);
+        song.channel = "Bad Channel".to_string();
+        assert!(matches!(
+            check_request(&limits, &playlist, &song, 0),
+            Err(RequestRejection::BlockedChannel { .. })
+        ));
+
+        let mut song = item("b", None);
+        song.title = "빗소리 10시간 연속재생".to_string();
+        assert_eq!(
+            check_request(&limits, &playlist, &song, 0),
+            Err(RequestRejection::BlockedKeyword {
+                keyword: "10시간".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_duplicates() {
+        let limits = PlaylistLimits {
+            prevent_duplicates: true,
+            duplicate_window_mins: 30,
+            ..PlaylistLimits::default()
+        };
+        let mut playlist = PlaylistState::new();
+        playlist.add_item(item("queued", None));
+        playlist.history.push_back(PlayedItem {
+            item: item("played", None),
+            played_at: 1_000,
+        });
+
+        assert_eq!(
+            check_request(&limits, &playlist, &item("queued", None), 1_000),
+            Err(RequestRejection::AlreadyQueued)
+        );
+        assert_eq!(
+            check_request(&limits, &playlist, &item("played", None), 1_000 + 10 * 60),
+            Err(RequestRejection::RecentlyPlayed { minutes_ago: 10 })
+        );
+        assert!(check_request(&limits, &playlist, &item("played", None), 1_000 + 31 * 60).is_ok());
+
+        let limits = PlaylistLimits::default();
+        assert!(check_request(&limits, &playlist, &item("queued", None), 1_000).is_ok());
+    }
+
+    #[test]
+    fn test_playability() {
+        let playable = VideoMetadata {
+            duration_secs: Some(200),
+            embeddable: true,
+            ..VideoMetadata::default()
+        };
+        let with = |metadata: VideoMetadata| PlaylistItem {
+            metadata: Some(metadata),
+            ..item("a", Some("3:20"))
+        };
+
+        assert!(check_playable(&item("a", None), "KR").is_ok());
+        assert!(check_playable(&with(playable.clone()), "KR").is_ok());
+        assert_eq!(
+            check_playable(
+                &with(VideoMetadata {
+                    embeddable: false,
+                    ..playable.clone()
+                }),
+                "KR"
+            ),
+            Err(RequestRejection::NotEmbeddable)
+        );
+        assert_eq!(
+            check_playable(
+                &with(VideoMetadata {
+                    age_restricted: true,
+                    unavailable_reason: Some("Sign in to confirm your age".to_string()),
+                    ..playable.clone()
+                }),
+                "KR"
+            ),
+            Err(RequestRejection::AgeRestricted)
+        );
+        assert!(matches!(
+            check_playable(
+                &with(VideoMetadata {
+                    unavailable_reason: Some("Video unavailable".to_string()),
+                    ..playable.clone()
+                }),
+                "KR"
+            ),
+            Err(RequestRejection::Unavailable { .. })
+        ));
+        let us_only = with(VideoMetadata {
+            available_countries: vec!["US".to_string()],
+            ..playable.clone()
+        });
+        assert!(check_playable(&us_only, "US").is_ok());
+        assert_eq!(
+            check_playable(&us_only, "KR").unwrap_err().message("Song"),
+            "'Song'은(는) KR 지역에서 볼 수 없는 영상입니다"
+        );
+
+        // 라이브 방송은 길이 제한이 있을 때만 거절
+        let live = with(VideoMetadata {
+            duration_secs: None,
+            is_live: true,
+            ..playable
+        });
+        let playlist = PlaylistState::new();
+        assert!(check_playable(&live, "KR").is_ok());
+        assert!(check_request(&PlaylistLimits::default(), &playlist, &live, 0).is_ok());
+        let limits = PlaylistLimits {
+            max_duration_secs: Some(600),
+            ..PlaylistLimits::default()
+        };
+        assert_eq!(
+            check_request(&limits, &playlist, &live, 0),
+            Err(RequestRejection::LiveStream)
+        );
+    }
+}
@@ -0,0 +1,139 @@
+use super::PlaylistState;
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+use tokio::sync::mpsc;
+
+// 마지막 변경 후 이 시간 동안 추가 변경이 없으면 저장
+const SAVE_DEBOUNCE: Duration = Duration::from_millis(500);
+
+#[derive(Clone)]
+pub struct PlaylistStore {
+    playlist_path: PathBuf,
+}
+
+impl PlaylistStore {
+    pub fn new(app_handle: &AppHandle) -> Result<Self, String> {
+        let app_data_dir = app_handle
+            .path()
+            .app_data_dir()
+            .map_err(|e| format!("Failed to get app data directory: {}", e))?;
+
+        // 디렉토리가 없으면 생성
+        if !app_data_dir.exists() {
+            fs::create_dir_all(&app_data_dir)
+                .map_err(|e| format!("Failed to create app data directory: {}", e))?;
+        }
+
+        Ok(Self::with_path(app_data_dir.join("playlist.json")))
+    }
+
+    pub fn with_path(playlist_path: PathBuf) -> Self {
+        Self { playlist_path }
+    }
+
+    // 저장된 플레이리스트가 없으면 None
+    pub fn load(&self) -> Result<Option<PlaylistState>, String> {
+        if !self.playlist_path.exists() {
+            return Ok(None);
+        }
+
+        let content = fs::read_to_string(&self.playlist_path)
+            .map_err(|e| format!("Failed to read playlist file: {}", e))?;
+
+        let mut playlist: PlaylistState = serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to parse playlist file: {}", e))?;
+        playlist.validate_restored();
+
+        Ok(Some(playlist))
+    }
+
+    pub fn save(&self, playlist: &PlaylistState) -> Result<(), String> {
+        let content = serde_json::to_string_pretty(playlist)
+            .map_err(|e| format!("Failed to serialize playlist: {}", e))?;
+
+        // 저장 도중 종료되어도 기존 파일이 깨지지 않도록 임시 파일에 먼저 기록
+        let temp_path = self.playlist_path.with_extension("json.tmp");
+        fs::write(&temp_path, content)
+            .map_err(|e| format!("Failed to write playlist file: {}", e))?;
+        fs::rename(&temp_path, &self.playlist_path)
+            .map_err(|e| format!("Failed to replace playlist file: {}", e))?;
+
+        Ok(())
+    }
+}
+
+// 변경될 때마다 받은 플레이리스트를 모아서 마지막 상태만 저장
+pub async fn run_playlist_saver(
+    store: PlaylistStore,
+    mut save_rx: mpsc::UnboundedReceiver<PlaylistState>,
+) {
+    while let Some(mut latest) = save_rx.recv().await {
+        // 채널이 닫히거나 대기 시간이 지날 때까지 최신 상태로 교체
+        while let Ok(Some(playlist)) = tokio::time::timeout(SAVE_DEBOUNCE, save_rx.recv()).await {
+            latest = playlist;
+        }
+
+        if let Err(e) = store.save(&latest) {
+            println!("[Playlist] Failed to save playlist: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::playlist::test_item;
+
+    fn temp_store() -> PlaylistStore {
+        let path = std::env::temp_dir().join(format!("playlist-{}.json", uuid::Uuid::new_v4()));
+        PlaylistStore::with_path(path)
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let store = temp_store();
+        assert!(store.load().unwrap().is_none());
+
+        let mut playlist = PlaylistState::new();
+        playlist.add_item(test_item("a"));
+        playlist.add_item(test_item("b"));
+        playlist.play_at(1);
+        playlist.set_autoplay(false);
+        store.save(&playlist).unwrap();
+
+        let restored = store.load().unwrap().unwrap();
+        assert_eq!(restored.items.len(), 2);
+        assert_eq!(restored.current_index, Some(1));
+        assert!(!restored.autoplay);
+        // 재시작 후에는 자동으로 재생하지 않음
+        assert!(!restored.is_playing);
+
+        let _ = fs::remove_file(&store.playlist_path);
+    }
+
+    #[tokio::test]
+    async fn test_debounced_saver() {
+        let store = temp_store();
+        let path = store.playlist_path.clone();
+        let (save_tx, save_rx) = mpsc::unbounded_channel();
+        let saver = tokio::spawn(run_playlist_saver(store, save_rx));
+
+        let mut playlist = PlaylistState::new();
+        for id in ["a", "b", "c"] {
+            playlist.add_item(test_item(id));
+            save_tx.send(playlist.clone()).unwrap();
+        }
+        drop(save_tx);
+        saver.await.unwrap();
+
+        let restored = PlaylistStore::with_path(path.clone())
+            .load()
+            .unwrap()
+            .unwrap();
+        assert_eq!(restored.items.len(), 3);
+
+        let _ = fs::remove_file(&path);
+    }
+}
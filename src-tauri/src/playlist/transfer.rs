@@ -0,0 +1,304 @@
+use super::{
+    extract_youtube_id, format_duration_secs, parse_duration_secs, ItemSource, PlaylistItem,
+    RequestPriority,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+const CSV_HEADER: [&str; 6] = [
+    "video_id", "title", "channel", "duration", "url", "added_by",
+];
+
+// 플레이리스트 내보내기/가져오기 형식
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PlaylistFormat {
+    Json,
+    M3u,
+    Csv,
+}
+
+pub fn export_items(
+    items: &VecDeque<PlaylistItem>,
+    format: PlaylistFormat,
+) -> Result<String, String> {
+    match format {
+        PlaylistFormat::Json => serde_json::to_string_pretty(items)
+            .map_err(|e| format!("Failed to serialize playlist: {}", e)),
+        PlaylistFormat::M3u => Ok(export_m3u(items)),
+        PlaylistFormat::Csv => Ok(export_csv(items)),
+    }
+}
+
+// 가져온 곡은 새 ID와 추가 시각을 받고, 신청자가 없으면 added_by로 기록
+pub fn import_items(
+    content: &str,
+    format: PlaylistFormat,
+    added_by: &str,
+    now: i64,
+) -> Result<Vec<PlaylistItem>, String> {
+    let items = match format {
+        PlaylistFormat::Json => serde_json::from_str::<Vec<PlaylistItem>>(content)
+            .map_err(|e| format!("플레이리스트 파일을 읽을 수 없습니다: {}", e))?,
+        PlaylistFormat::M3u => import_m3u(content),
+        PlaylistFormat::Csv => import_csv(content)?,
+    };
+
+    if items.is_empty() {
+        return Err("가져올 YouTube 영상이 없습니다".to_string());
+    }
+
+    Ok(items
+        .into_iter()
+        .map(|item| PlaylistItem {
+            id: uuid::Uuid::new_v4().to_string(),
+            added_by: if item.added_by.trim().is_empty() {
+                added_by.to_string()
+            } else {
+                item.added_by
+            },
+            // 다른 방송의 uid가 지금 시청자와 겹치지 않도록 신청자 정보는 닉네임만 남김
+            requester_id: None,
+            added_at: now,
+            priority: RequestPriority::Normal,
+            source: ItemSource::Request,
+            ..item
+        })
+        .collect())
+}
+
+// 파일에 없는 정보는 영상 ID로 채움
+fn imported_item(
+    video_id: String,
+    title: Option<String>,
+    channel: Option<String>,
+    duration: Option<String>,
+    added_by: Option<String>,
+) -> PlaylistItem {
+    PlaylistItem {
+        id: String::new(),
+        title: title.unwrap_or_else(|| format!("Video {}", video_id)),
+        channel: channel.unwrap_or_else(|| "Unknown Channel".to_string()),
+        duration,
+        thumbnail: Some(format!(
+            "https://img.youtube.com/vi/{}/mqdefault.jpg",
+            video_id
+        )),
+        url: format!("https://www.youtube.com/watch?v={}", video_id),
+        added_by: added_by.unwrap_or_default(),
+        requester_id: None,
+        added_at: 0,
+        priority: RequestPriority::Normal,
+        source: ItemSource::Request,
+        metadata: None,
+        start_seconds: None,
+        video_id,
+    }
+}
+
+fn export_m3u(items: &VecDeque<PlaylistItem>) -> String {
+    let mut out = String::from("#EXTM3U\n");
+    for item in items {
+        let secs = item
+            .duration
+            .as_deref()
+            .and_then(parse_duration_secs)
+            .map_or(-1, |secs| secs as i64);
+        out.push_str(&format!(
+            "#EXTINF:{},{} - {}\n{}\n",
+            secs, item.channel, item.title, item.url
+        ));
+    }
+    out
+}
+
+// #EXTINF:<초>,<채널> - <제목> 다음 줄의 YouTube 주소만 가져옴
+fn import_m3u(content: &str) -> Vec<PlaylistItem> {
+    let mut items = Vec::new();
+    let mut info: Option<(Option<String>, String)> = None;
+
+    for line in content.lines().map(str::trim) {
+        if let Some(extinf) = line.strip_prefix("#EXTINF:") {
+            let (secs, name) = extinf.split_once(',').unwrap_or((extinf, ""));
+            let duration = secs.trim().parse::<u64>().ok().map(format_duration_secs);
+            info = Some((duration, name.trim().to_string()));
+            continue;
+        }
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let (duration, name) = info.take().unwrap_or_default();
+        let Some(video_id) = extract_youtube_id(line) else {
+            continue;
+        };
+        let (channel, title) = match name.split_once(" - ") {
+            Some((channel, title)) => (Some(channel.to_string()), Some(title.to_string())),
+            None => (None, Some(name).filter(|name| !name.is_empty())),
+        };
+        items.push(imported_item(video_id, title, channel, duration, None));
+    }
+
+    items
+}
+
+fn export_csv(items: &VecDeque<PlaylistItem>) -> String {
+    let mut out = CSV_HEADER.join(",");
+    out.push('\n');
+    for item in items {
+        let row = [
+            item.video_id.as_str(),
+            item.title.as_str(),
+            item.channel.as_str(),
+            item.duration.as_deref().unwrap_or(""),
+            item.url.as_str(),
+            item.added_by.as_str(),
+        ];
+        let row: Vec<String> = row.iter().map(|field| csv_field(field)).collect();
+        out.push_str(&row.join(","));
+        out.push('\n');
+    }
+    out
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+// 따옴표로 감싼 필드(쉼표, 줄바꿈, "" 포함)를 지원하는 CSV 파서
+fn parse_csv(content: &str) -> Vec<Vec<String>> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = content.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match (c, in_quotes) {
+            ('"', true) if chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            ('"', _) => in_quotes = !in_quotes,
+            (',', false) => row.push(std::mem::take(&mut field)),
+            ('\r', false) => {}
+            ('\n', false) => {
+                row.push(std::mem::take(&mut field));
+                rows.push(std::mem::take(&mut row));
+            }
+            _ => field.push(c),
+        }
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+
+    rows.retain(|row| row.iter().any(|field| !field.trim().is_empty()));
+    rows
+}
+
+// 헤더 이름으로 열을 찾으므로 video_id 또는 url 열만 있으면 됨
+fn import_csv(content: &str) -> Result<Vec<PlaylistItem>, String> {
+    let mut rows = parse_csv(content).into_iter();
+    let header: Vec<String> = rows
+        .next()
+        .ok_or_else(|| "CSV 파일이 비어 있습니다".to_string())?
+        .iter()
+        .map(|name| name.trim().to_lowercase())
+        .collect();
+    let column = |name: &str| header.iter().position(|column| column == name);
+    let (video_id_col, url_col) = (column("video_id"), column("url"));
+    if video_id_col.is_none() && url_col.is_none() {
+        return Err("CSV에 video_id 또는 url 열이 필요합니다".to_string());
+    }
+    let (title_col, channel_col) = (column("title"), column("channel"));
+    let (duration_col, added_by_col) = (column("duration"), column("added_by"));
+
+    let items = rows
+        .filter_map(|row| {
+            let get = |col: Option<usize>| {
+                col.and_then(|col| row.get(col))
+                    .map(|value| value.trim().to_string())
+                    .filter(|value| !value.is_empty())
+            };
+            let video_id = get(video_id_col)
+                .or_else(|| get(url_col).as_deref().and_then(extract_youtube_id))?;
+            Some(imported_item(
+                video_id,
+                get(title_col),
+                get(channel_col),
+                get(duration_col),
+                get(added_by_col),
+            ))
+        })
+        .collect();
+    Ok(items)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::playlist::test_item;
+
+    fn item(video_id: &str, title: &str) -> PlaylistItem {
+        PlaylistItem {
+            title: title.to_string(),
+            duration: Some("3:45".to_string()),
+            url: format!("https://www.youtube.com/watch?v={}", video_id),
+            priority: RequestPriority::Donation { amount: 1000 },
+            ..test_item(video_id)
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let items = VecDeque::from([item("aaa", "First"), item("bbb", "Say \"hi\", again")]);
+
+        for format in [
+            PlaylistFormat::Json,
+            PlaylistFormat::M3u,
+            PlaylistFormat::Csv,
+        ] {
+            let exported = export_items(&items, format).unwrap();
+            let imported = import_items(&exported, format, "App User", 100).unwrap();
+
+            assert_eq!(imported.len(), 2, "{:?}", format);
+            assert_eq!(imported[1].video_id, "bbb");
+            assert_eq!(imported[1].title, "Say \"hi\", again");
+            assert_eq!(imported[1].channel, "Channel");
+            assert_eq!(imported[1].duration.as_deref(), Some("3:45"));
+            assert_eq!(imported[1].added_at, 100);
+            assert_eq!(imported[1].priority, RequestPriority::Normal);
+            assert_ne!(imported[1].id, "bbb");
+        }
+    }
+
+    #[test]
+    fn test_import_m3u_skips_other_entries() {
+        let m3u = "#EXTM3U\n#EXTINF:-1,Just a title\nhttps://youtu.be/abc123\n/music/local.mp3\nhttps://www.youtube.com/watch?v=def456\n";
+        let items = import_items(m3u, PlaylistFormat::M3u, "App User", 0).unwrap();
+
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].title, "Just a title");
+        assert_eq!(items[0].duration, None);
+        assert_eq!(items[0].added_by, "App User");
+        assert_eq!(items[1].title, "Video def456");
+    }
+
+    #[test]
+    fn test_import_csv_by_url_column() {
+        let csv = "url,title\nhttps://youtu.be/abc123,Song\n,missing\n";
+        let items = import_items(csv, PlaylistFormat::Csv, "App User", 0).unwrap();
+
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].video_id, "abc123");
+        assert_eq!(items[0].title, "Song");
+
+        assert!(import_items("title\nSong\n", PlaylistFormat::Csv, "App User", 0).is_err());
+    }
+}
@@ -0,0 +1,259 @@
+use super::{PlaylistItem, PlaylistState};
+use std::collections::VecDeque;
+
+// 되돌리기 기록 최대 개수
+const UNDO_LIMIT: usize = 50;
+
+// 되돌릴 수 있는 플레이리스트 변경 (곡은 ID로 찾으므로 그 사이 재생이 진행되어도 적용 가능)
+#[derive(Debug, Clone)]
+pub enum PlaylistChange {
+    Added {
+        items: Vec<PlaylistItem>,
+    },
+    Removed {
+        item: Box<PlaylistItem>,
+        index: usize,
+    },
+    Moved {
+        id: String,
+        from: usize,
+        to: usize,
+    },
+    Cleared {
+        items: VecDeque<PlaylistItem>,
+        current_index: Option<usize>,
+        // 비우기 전에 재생을 시작한 곡 (되돌리면 이미 재생한 곡으로 복원)
+        started_id: Option<String>,
+    },
+    PlayedAt {
+        previous: Option<String>,
+        next: String,
+    },
+}
+
+impl PlaylistChange {
+    pub fn describe(&self) -> String {
+        match self {
+            Self::Added { items } => match items.as_slice() {
+                [item] => format!("'{}' 추가", item.title),
+                [item, rest @ ..] => format!("'{}' 외 {}곡 추가", item.title, rest.len()),
+                [] => "곡 추가".to_string(),
+            },
+            Self::Removed { item, .. } => format!("'{}' 삭제", item.title),
+            Self::Moved { .. } => "순서 변경".to_string(),
+            Self::Cleared { .. } => "목록 비우기".to_string(),
+            Self::PlayedAt { .. } => "곡 선택".to_string(),
+        }
+    }
+
+    fn revert(&self, playlist: &mut PlaylistState) -> Result<(), String> {
+        match self {
+            Self::Added { items } => {
+                let positions: Vec<usize> = items
+                    .iter()
+                    .filter_map(|item| playlist.position_of(&item.id))
+                    .collect();
+                if positions.is_empty() {
+                    return Err(missing());
+                }
+                for id in items.iter().map(|item| &item.id) {
+                    if let Some(position) = playlist.position_of(id) {
+                        playlist.remove_item(position);
+                    }
+                }
+            }
+            Self::Removed { item, index } => {
+                if playlist.position_of(&item.id).is_some() {
+                    return Err(missing());
+                }
+                playlist.insert_item(*index, item.as_ref().clone());
+            }
+            Self::Moved { id, from, .. } => {
+                let position = playlist.position_of(id).ok_or_else(missing)?;
+                let last = playlist.items.len() - 1;
+                playlist.move_item(position, (*from).min(last))?;
+            }
+            Self::Cleared {
+                items,
+                current_index,
+                started_id,
+            } => playlist.restore_cleared(items.clone(), *current_index, started_id.clone()),
+            Self::PlayedAt { previous, .. } => {
+                let position = previous
+                    .as_deref()
+                    .and_then(|id| playlist.position_of(id))
+                    .ok_or_else(missing)?;
+                playlist.play_at(position);
+            }
+        }
+        Ok(())
+    }
+
+    fn reapply(&self, playlist: &mut PlaylistState) -> Result<(), String> {
+        match self {
+            Self::Added { items } => {
+                let missing_items: Vec<PlaylistItem> = items
+                    .iter()
+                    .filter(|item| playlist.position_of(&item.id).is_none())
+                    .cloned()
+                    .collect();
+                if missing_items.is_empty() {
+                    return Err(missing());
+                }
+                for item in missing_items {
+                    playlist.add_item(item);
+                }
+            }
+            Self::Removed { item, .. } => {
+                let position = playlist.position_of(&item.id).ok_or_else(missing)?;
+                playlist.remove_item(position);
+            }
+            Self::Moved { id, to, .. } => {
+                let position = playlist.position_of(id).ok_or_else(missing)?;
+                let last = playlist.items.len() - 1;
+                playlist.move_item(position, (*to).min(last))?;
+            }
+            Self::Cleared { .. } => playlist.clear(),
+            Self::PlayedAt { next, .. } => {
+                let position = playlist.position_of(next).ok_or_else(missing)?;
+                playlist.play_at(position);
+            }
+        }
+        Ok(())
+    }
+}
+
+fn missing() -> String {
+    "해당 곡이 이미 목록에서 바뀌어 되돌릴 수 없습니다".to_string()
+}
+
+// 되돌리기/다시 실행 기록 (새 변경이 기록되면 다시 실행 기록은 지움)
+#[derive(Debug, Default)]
+pub struct UndoHistory {
+    undo: VecDeque<PlaylistChange>,
+    redo: Vec<PlaylistChange>,
+}
+
+impl UndoHistory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&mut self, change: PlaylistChange) {
+        self.undo.push_back(change);
+        if self.undo.len() > UNDO_LIMIT {
+            self.undo.pop_front();
+        }
+        self.redo.clear();
+    }
+
+    // 적용할 수 없는 변경은 기록에서 버리고 오류를 반환
+    pub fn undo(&mut self, playlist: &mut PlaylistState) -> Result<String, String> {
+        let change = self
+            .undo
+            .pop_back()
+            .ok_or_else(|| "되돌릴 변경이 없습니다".to_string())?;
+        change.revert(playlist)?;
+        let description = change.describe();
+        self.redo.push(change);
+        Ok(description)
+    }
+
+    pub fn redo(&mut self, playlist: &mut PlaylistState) -> Result<String, String> {
+        let change = self
+            .redo
+            .pop()
+            .ok_or_else(|| "다시 실행할 변경이 없습니다".to_string())?;
+        change.reapply(playlist)?;
+        let description = change.describe();
+        self.undo.push_back(change);
+        if self.undo.len() > UNDO_LIMIT {
+            self.undo.pop_front();
+        }
+        Ok(description)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::playlist::{test_item, test_playlist};
+
+    fn ids(playlist: &PlaylistState) -> Vec<&str> {
+        playlist.items.iter().map(|item| item.id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_undo_redo_remove_and_move() {
+        let mut playlist = test_playlist(&["a", "b", "c"]);
+        let mut history = UndoHistory::new();
+
+        let removed = playlist.remove_item(1).unwrap();
+        history.record(PlaylistChange::Removed {
+            item: Box::new(removed),
+            index: 1,
+        });
+        playlist.move_item(1, 0).unwrap();
+        history.record(PlaylistChange::Moved {
+            id: "c".to_string(),
+            from: 1,
+            to: 0,
+        });
+        assert_eq!(ids(&playlist), ["c", "a"]);
+
+        history.undo(&mut playlist).unwrap();
+        assert_eq!(ids(&playlist), ["a", "c"]);
+        assert_eq!(history.undo(&mut playlist).unwrap(), "'Song b' 삭제");
+        assert_eq!(ids(&playlist), ["a", "b", "c"]);
+        assert!(history.undo(&mut playlist).is_err());
+
+        history.redo(&mut playlist).unwrap();
+        assert_eq!(ids(&playlist), ["a", "c"]);
+    }
+
+    #[test]
+    fn test_undo_clear_keeps_new_items() {
+        let mut playlist = test_playlist(&["a", "b"]);
+        playlist.play_at(1);
+        let mut history = UndoHistory::new();
+
+        history.record(PlaylistChange::Cleared {
+            items: playlist.items.clone(),
+            current_index: playlist.current_index,
+            started_id: playlist.started_id.clone(),
+        });
+        playlist.clear();
+        playlist.add_item(test_item("c"));
+
+        history.undo(&mut playlist).unwrap();
+        assert_eq!(ids(&playlist), ["a", "b", "c"]);
+        assert_eq!(playlist.current_index, Some(1));
+        // 이미 재생을 시작했던 곡은 다시 대기 곡으로 세지 않음
+        let upcoming: Vec<&str> = playlist
+            .upcoming()
+            .iter()
+            .map(|item| item.id.as_str())
+            .collect();
+        assert_eq!(upcoming, ["c"]);
+    }
+
+    #[test]
+    fn test_undo_add_after_playback_moved_on() {
+        let mut playlist = test_playlist(&["a", "b"]);
+        let mut history = UndoHistory::new();
+        playlist.add_item(test_item("c"));
+        history.record(PlaylistChange::Added {
+            items: vec![test_item("c")],
+        });
+        playlist.play_at(1);
+
+        // 그 사이 재생이 진행되어도 추가한 곡만 빠짐
+        history.undo(&mut playlist).unwrap();
+        assert_eq!(ids(&playlist), ["a", "b"]);
+        assert_eq!(playlist.current_item().unwrap().id, "b");
+
+        playlist.remove_item(0);
+        history.redo(&mut playlist).unwrap();
+        assert_eq!(ids(&playlist), ["b", "c"]);
+    }
+}
@@ -0,0 +1,166 @@
+use super::PlaylistItem;
+use crate::commands::{VoteSkipConfig, VoteSkipThreshold};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
+
+// UI에 보내는 투표 진행 상황
+#[derive(Debug, Clone, Serialize)]
+pub struct VoteSkipProgress {
+    pub item_id: String,
+    pub title: String,
+    pub votes: usize,
+    pub required: usize,
+    pub passed: bool,
+}
+
+#[derive(Debug, Clone)]
+pub enum VoteOutcome {
+    Counted(VoteSkipProgress),
+    AlreadyVoted(VoteSkipProgress),
+    Passed(VoteSkipProgress),
+}
+
+// 현재 곡에 대한 건너뛰기 투표와 최근 채팅 참여자 기록
+#[derive(Debug, Default)]
+pub struct VoteSkipTracker {
+    item_id: Option<String>,
+    voters: HashSet<String>,
+    last_active: HashMap<String, Instant>,
+}
+
+impl VoteSkipTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record_activity(&mut self, user_id: &str) {
+        self.last_active.insert(user_id.to_string(), Instant::now());
+    }
+
+    // 설정된 시간 안에 채팅한 사람 수 (오래된 기록은 정리)
+    fn active_chatters(&mut self, window: Duration) -> usize {
+        let now = Instant::now();
+        self.last_active
+            .retain(|_, last| now.duration_since(*last) <= window);
+        self.last_active.len()
+    }
+
+    pub fn required_votes(&mut self, config: &VoteSkipConfig) -> usize {
+        match config.threshold {
+            VoteSkipThreshold::Count { votes } => votes.max(1),
+            VoteSkipThreshold::Percent { percent } => {
+                let active = self.active_chatters(Duration::from_secs(config.active_window_secs));
+                let required = (active * usize::from(percent)).div_ceil(100);
+                required.max(config.min_votes).max(1)
+            }
+        }
+    }
+
+    // 곡이 바뀌었으면 이전 투표는 버리고 새로 집계
+    pub fn vote(
+        &mut self,
+        item: &PlaylistItem,
+        user_id: &str,
+        config: &VoteSkipConfig,
+    ) -> VoteOutcome {
+        if self.item_id.as_deref() != Some(item.id.as_str()) {
+            self.item_id = Some(item.id.clone());
+            self.voters.clear();
+        }
+
+        // 투표한 사람도 참여자로 계산
+        self.record_activity(user_id);
+        let is_new = self.voters.insert(user_id.to_string());
+        let required = self.required_votes(config);
+        let votes = self.voters.len();
+        let progress = VoteSkipProgress {
+            item_id: item.id.clone(),
+            title: item.title.clone(),
+            votes,
+            required,
+            passed: votes >= required,
+        };
+
+        if progress.passed {
+            self.reset();
+            VoteOutcome::Passed(progress)
+        } else if is_new {
+            VoteOutcome::Counted(progress)
+        } else {
+            VoteOutcome::AlreadyVoted(progress)
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.item_id = None;
+        self.voters.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::playlist::test_item;
+
+    fn count_config(votes: usize) -> VoteSkipConfig {
+        VoteSkipConfig {
+            threshold: VoteSkipThreshold::Count { votes },
+            ..VoteSkipConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_votes_are_unique_per_user() {
+        let mut tracker = VoteSkipTracker::new();
+        let config = count_config(2);
+        let song = test_item("a");
+
+        assert!(matches!(
+            tracker.vote(&song, "user1", &config),
+            VoteOutcome::Counted(VoteSkipProgress { votes: 1, .. })
+        ));
+        assert!(matches!(
+            tracker.vote(&song, "user1", &config),
+            VoteOutcome::AlreadyVoted(VoteSkipProgress { votes: 1, .. })
+        ));
+        assert!(matches!(
+            tracker.vote(&song, "user2", &config),
+            VoteOutcome::Passed(VoteSkipProgress { votes: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn test_votes_reset_when_track_changes() {
+        let mut tracker = VoteSkipTracker::new();
+        let config = count_config(2);
+
+        tracker.vote(&test_item("a"), "user1", &config);
+        assert!(matches!(
+            tracker.vote(&test_item("b"), "user2", &config),
+            VoteOutcome::Counted(VoteSkipProgress { votes: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn test_percent_threshold() {
+        let mut tracker = VoteSkipTracker::new();
+        let config = VoteSkipConfig {
+            threshold: VoteSkipThreshold::Percent { percent: 50 },
+            active_window_secs: 300,
+            min_votes: 1,
+        };
+
+        for i in 0..5 {
+            tracker.record_activity(&format!("user{}", i));
+        }
+        // 참여자 5명의 50% → 3표
+        assert_eq!(tracker.required_votes(&config), 3);
+
+        let config = VoteSkipConfig {
+            min_votes: 4,
+            ..config
+        };
+        assert_eq!(tracker.required_votes(&config), 4);
+    }
+}
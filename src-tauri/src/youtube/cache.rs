@@ -0,0 +1,364 @@
+use super::{VideoMetadata, YouTubeVideo};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// 영상 정보는 제목이나 재생 가능 여부가 바뀔 수 있어서 오래 두지 않음
+const VIDEO_TTL_SECS: i64 = 3 * 24 * 60 * 60;
+// 같은 검색어라도 검색 결과 순위는 자주 바뀜
+const QUERY_TTL_SECS: i64 = 24 * 60 * 60;
+const MAX_VIDEOS: usize = 2000;
+const MAX_QUERIES: usize = 1000;
+// 상한을 넘으면 한 번에 이 비율까지 줄임 (넣을 때마다 정렬하지 않도록)
+const EVICT_TO_PERCENT: usize = 90;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedVideo {
+    video: YouTubeVideo,
+    cached_at: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedQuery {
+    video_ids: Vec<String>,
+    // 요청한 개수보다 결과가 적었으면 더 많이 요청해도 같은 결과
+    #[serde(default)]
+    exhausted: bool,
+    cached_at: i64,
+}
+
+// 이번 실행 동안의 캐시 적중 횟수와 현재 저장된 항목 수
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct CacheStats {
+    pub video_hits: u64,
+    pub video_misses: u64,
+    pub metadata_hits: u64,
+    pub metadata_misses: u64,
+    pub query_hits: u64,
+    pub query_misses: u64,
+    pub videos: usize,
+    pub queries: usize,
+}
+
+// 영상 ID → 영상 정보, 검색어 → 영상 ID 캐시 (앱 데이터 디렉토리에 저장)
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct MetadataCache {
+    #[serde(default)]
+    videos: HashMap<String, CachedVideo>,
+    #[serde(default)]
+    queries: HashMap<String, CachedQuery>,
+    #[serde(skip)]
+    path: Option<PathBuf>,
+    #[serde(skip)]
+    stats: CacheStats,
+    // 마지막 저장 이후 바뀐 내용이 있는지
+    #[serde(skip)]
+    dirty: bool,
+}
+
+impl MetadataCache {
+    pub fn with_path(path: PathBuf) -> Self {
+        Self {
+            path: Some(path),
+            ..Self::default()
+        }
+    }
+
+    // 저장된 캐시가 없으면 빈 캐시
+    pub fn load(path: PathBuf) -> Result<Self, String> {
+        if !path.exists() {
+            return Ok(Self::with_path(path));
+        }
+
+        let content =
+            fs::read_to_string(&path).map_err(|e| format!("Failed to read cache file: {}", e))?;
+        let cache: Self = serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to parse cache file: {}", e))?;
+        Ok(Self {
+            path: Some(path),
+            ..cache
+        })
+    }
+
+    // 바뀐 내용이 있으면 저장할 경로와 내용을 꺼냄 (파일 쓰기는 잠금 밖에서)
+    pub fn take_changes(&mut self) -> Result<Option<(PathBuf, String)>, String> {
+        let Some(path) = self.path.clone().filter(|_| self.dirty) else {
+            return Ok(None);
+        };
+
+        let content =
+            serde_json::to_string(self).map_err(|e| format!("Failed to serialize cache: {}", e))?;
+        self.dirty = false;
+        Ok(Some((path, content)))
+    }
+
+    // 저장에 실패하면 다음 저장 때 다시 시도
+    pub fn mark_dirty(&mut self) {
+        self.dirty = true;
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            videos: self.videos.len(),
+            queries: self.queries.len(),
+            ..self.stats
+        }
+    }
+
+    fn fresh_video(&self, video_id: &str, now: i64) -> Option<&YouTubeVideo> {
+        self.videos
+            .get(video_id)
+            .filter(|cached| now - cached.cached_at < VIDEO_TTL_SECS)
+            .map(|cached| &cached.video)
+    }
+
+    pub fn video(&mut self, video_id: &str, now: i64) -> Option<YouTubeVideo> {
+        let video = self.fresh_video(video_id, now).cloned();
+        self.count_video(video.is_some());
+        video
+    }
+
+    // 영상 페이지에서 확인한 정보까지 저장된 경우만 적중
+    pub fn metadata(&mut self, video_id: &str, now: i64) -> Option<VideoMetadata> {
+        let metadata = self
+            .fresh_video(video_id, now)
+            .and_then(|video| video.metadata.clone());
+        if metadata.is_some() {
+            self.stats.metadata_hits += 1;
+        } else {
+            self.stats.metadata_misses += 1;
+        }
+        metadata
+    }
+
+    fn count_video(&mut self, hit: bool) {
+        if hit {
+            self.stats.video_hits += 1;
+        } else {
+            self.stats.video_misses += 1;
+        }
+    }
+
+    // 새로 받은 정보에 없는 길이/재생 정보는 기존 값을 유지
+    pub fn insert_video(&mut self, video: &YouTubeVideo, now: i64) {
+        let previous = self.fresh_video(&video.video_id, now);
+        let video = YouTubeVideo {
+            duration: video
+                .duration
+                .clone()
+                .or_else(|| previous.and_then(|previous| previous.duration.clone())),
+            metadata: video
+                .metadata
+                .clone()
+                .or_else(|| previous.and_then(|previous| previous.metadata.clone())),
+            // 시작 위치는 신청 링크마다 다름
+            start_seconds: None,
+            ..video.clone()
+        };
+        self.videos.insert(
+            video.video_id.clone(),
+            CachedVideo {
+                video,
+                cached_at: now,
+            },
+        );
+        evict_oldest(&mut self.videos, MAX_VIDEOS, |cached| cached.cached_at);
+        self.dirty = true;
+    }
+
+    // 영상 정보 없이 재생 정보만 확인한 경우나 만료된 영상은 저장하지 않음
+    // (제목 등은 새로 받지 않았으므로 저장 시각도 그대로 둠)
+    pub fn set_metadata(&mut self, video_id: &str, metadata: VideoMetadata, now: i64) {
+        if let Some(cached) = self
+            .videos
+            .get_mut(video_id)
+            .filter(|cached| now - cached.cached_at < VIDEO_TTL_SECS)
+        {
+            cached.video.metadata = Some(metadata);
+            self.dirty = true;
+        }
+    }
+
+    // 이전에 더 적은 결과만 요청했거나 영상 정보가 만료되었으면 다시 검색
+    pub fn search(&mut self, query: &str, limit: usize, now: i64) -> Option<Vec<YouTubeVideo>> {
+        let videos = self
+            .queries
+            .get(&normalize_query(query))
+            .filter(|cached| now - cached.cached_at < QUERY_TTL_SECS)
+            .filter(|cached| cached.video_ids.len() >= limit || cached.exhausted)
+            .and_then(|cached| {
+                cached
+                    .video_ids
+                    .iter()
+                    .take(limit)
+                    .map(|id| self.fresh_video(id, now).cloned())
+                    .collect::<Option<Vec<_>>>()
+            });
+
+        if videos.is_some() {
+            self.stats.query_hits += 1;
+        } else {
+            self.stats.query_misses += 1;
+        }
+        videos
+    }
+
+    // limit개를 요청해서 받은 검색 결과
+    pub fn insert_search(&mut self, query: &str, videos: &[YouTubeVideo], limit: usize, now: i64) {
+        for video in videos {
+            self.insert_video(video, now);
+        }
+        self.queries.insert(
+            normalize_query(query),
+            CachedQuery {
+                video_ids: videos.iter().map(|video| video.video_id.clone()).collect(),
+                exhausted: videos.len() < limit,
+                cached_at: now,
+            },
+        );
+        evict_oldest(&mut self.queries, MAX_QUERIES, |cached| cached.cached_at);
+        self.dirty = true;
+    }
+}
+
+// 저장 도중 종료되어도 기존 파일이 깨지지 않도록 임시 파일에 먼저 기록
+pub fn write_file(path: &Path, content: &str) -> Result<(), String> {
+    let temp_path = path.with_extension("json.tmp");
+    fs::write(&temp_path, content).map_err(|e| format!("Failed to write cache file: {}", e))?;
+    fs::rename(&temp_path, path).map_err(|e| format!("Failed to replace cache file: {}", e))?;
+    Ok(())
+}
+
+// 대소문자와 공백만 다른 검색어는 같은 검색어로 봄
+fn normalize_query(query: &str) -> String {
+    query
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+fn evict_oldest<T>(entries: &mut HashMap<String, T>, max: usize, cached_at: impl Fn(&T) -> i64) {
+    if entries.len() <= max {
+        return;
+    }
+    let mut by_age: Vec<(i64, String)> = entries
+        .iter()
+        .map(|(key, entry)| (cached_at(entry), key.clone()))
+        .collect();
+    by_age.sort();
+    let excess = entries.len() - max * EVICT_TO_PERCENT / 100;
+    for (_, key) in by_age.into_iter().take(excess) {
+        entries.remove(&key);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::youtube::test_video;
+
+    #[test]
+    fn test_video_and_metadata() {
+        let mut cache = MetadataCache::default();
+        assert!(cache.video("a", 0).is_none());
+
+        cache.insert_video(
+            &YouTubeVideo {
+                start_seconds: Some(30),
+                ..test_video("a")
+            },
+            0,
+        );
+        let cached = cache.video("a", 10).unwrap();
+        assert_eq!(cached.title, "Song a");
+        assert_eq!(cached.start_seconds, None);
+        // 재생 정보는 아직 확인하지 않음
+        assert!(cache.metadata("a", 10).is_none());
+
+        let metadata = VideoMetadata {
+            duration_secs: Some(200),
+            embeddable: true,
+            ..VideoMetadata::default()
+        };
+        cache.set_metadata("a", metadata.clone(), 20);
+        // 검색 결과로 다시 저장되어도 재생 정보는 유지
+        cache.insert_video(&test_video("a"), 30);
+        assert_eq!(cache.metadata("a", 40), Some(metadata));
+
+        // 만료된 영상은 다시 받아야 함
+        assert!(cache.video("a", 30 + VIDEO_TTL_SECS).is_none());
+
+        // 재생 정보를 붙여도 영상 정보의 저장 시각은 그대로
+        cache.insert_video(&test_video("b"), 0);
+        cache.set_metadata("b", VideoMetadata::default(), 10);
+        assert!(cache.video("b", VIDEO_TTL_SECS).is_none());
+        // 만료된 영상에는 붙이지 않음
+        cache.set_metadata("b", VideoMetadata::default(), VIDEO_TTL_SECS);
+        cache.insert_video(&test_video("b"), VIDEO_TTL_SECS);
+        assert!(cache.metadata("b", VIDEO_TTL_SECS).is_none());
+
+        let stats = cache.stats();
+        assert_eq!((stats.video_hits, stats.video_misses), (1, 3));
+        assert_eq!((stats.metadata_hits, stats.metadata_misses), (1, 2));
+        assert_eq!(stats.videos, 2);
+    }
+
+    #[test]
+    fn test_search_queries() {
+        let mut cache = MetadataCache::default();
+        assert!(cache.search("IU Blueming", 1, 0).is_none());
+
+        cache.insert_search("IU Blueming", &[test_video("a"), test_video("b")], 2, 0);
+        let hit = cache.search("  iu   blueming ", 1, 10).unwrap();
+        assert_eq!(hit.len(), 1);
+        assert_eq!(hit[0].video_id, "a");
+        // 저장된 것보다 많은 결과가 필요하면 다시 검색
+        assert!(cache.search("iu blueming", 3, 10).is_none());
+        assert!(cache.search("iu blueming", 1, QUERY_TTL_SECS).is_none());
+
+        // 요청한 것보다 결과가 적었던 검색어는 더 많이 요청해도 적중
+        cache.insert_search("rare song", &[test_video("c")], 5, 0);
+        assert_eq!(cache.search("rare song", 10, 10).unwrap().len(), 1);
+
+        let stats = cache.stats();
+        assert_eq!((stats.query_hits, stats.query_misses), (2, 3));
+        assert_eq!((stats.videos, stats.queries), (3, 2));
+    }
+
+    #[test]
+    fn test_size_bound_and_persistence() {
+        let mut cache = MetadataCache::default();
+        for i in 0..MAX_VIDEOS + 5 {
+            cache.insert_video(&test_video(&i.to_string()), i as i64);
+        }
+        // 상한을 넘으면 가장 오래된 항목부터 한 번에 여러 개 지움
+        let kept = MAX_VIDEOS * EVICT_TO_PERCENT / 100;
+        assert_eq!(cache.stats().videos, kept + 4);
+        assert!(cache.fresh_video("0", 0).is_none());
+        assert!(cache
+            .fresh_video(&(MAX_VIDEOS - kept).to_string(), 0)
+            .is_none());
+        assert!(cache
+            .fresh_video(&(MAX_VIDEOS - kept + 1).to_string(), 0)
+            .is_some());
+
+        let path =
+            std::env::temp_dir().join(format!("youtube-cache-{}.json", uuid::Uuid::new_v4()));
+        let mut cache = MetadataCache::load(path.clone()).unwrap();
+        // 바뀐 내용이 없으면 저장할 것도 없음
+        assert!(cache.take_changes().unwrap().is_none());
+        cache.insert_search("query", &[test_video("a")], 1, 0);
+        let (saved_path, content) = cache.take_changes().unwrap().unwrap();
+        assert_eq!(saved_path, path);
+        write_file(&path, &content).unwrap();
+        assert!(cache.take_changes().unwrap().is_none());
+
+        let mut restored = MetadataCache::load(path.clone()).unwrap();
+        assert_eq!(restored.search("query", 1, 10).unwrap()[0].video_id, "a");
+        // 적중 횟수는 저장하지 않음
+        assert_eq!(restored.stats().query_hits, 1);
+        let _ = fs::remove_file(path);
+    }
+}
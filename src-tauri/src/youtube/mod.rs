@@ -0,0 +1,589 @@
+pub mod cache;
+
+use cache::{CacheStats, MetadataCache};
+use reqwest;
+use serde::{Deserialize, Serialize};
+use std::sync::{Mutex, MutexGuard};
+use tokio::sync::mpsc;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct YouTubeVideo {
+    pub video_id: String,
+    pub title: String,
+    pub channel: String,
+    pub duration: Option<String>,
+    pub thumbnail: Option<String>,
+    pub url: String,
+    #[serde(default)]
+    pub metadata: Option<VideoMetadata>,
+    // 신청 링크의 시작 위치
+    #[serde(default)]
+    pub start_seconds: Option<u64>,
+}
+
+// 영상 페이지에서 확인한 재생 가능 여부와 길이
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VideoMetadata {
+    // 라이브/예정 방송은 길이가 없음
+    pub duration_secs: Option<u64>,
+    pub is_live: bool,
+    pub is_upcoming: bool,
+    pub embeddable: bool,
+    pub age_restricted: bool,
+    // 볼 수 있는 국가 코드 (비어 있으면 지역 제한 없음)
+    #[serde(default)]
+    pub available_countries: Vec<String>,
+    // 삭제, 비공개 등으로 재생할 수 없으면 YouTube가 알려준 이유
+    pub unavailable_reason: Option<String>,
+}
+
+impl VideoMetadata {
+    pub fn is_blocked_in(&self, region: &str) -> bool {
+        !self.available_countries.is_empty()
+            && !self
+                .available_countries
+                .iter()
+                .any(|country| country.eq_ignore_ascii_case(region))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct YouTubeSearchResult {
+    pub videos: Vec<YouTubeVideo>,
+}
+
+pub struct YouTubeService {
+    client: reqwest::Client,
+    cache: Mutex<MetadataCache>,
+    // 캐시가 바뀌었다고 알림 (받는 쪽에서 모아서 save_cache 호출)
+    cache_changed_tx: mpsc::UnboundedSender<()>,
+}
+
+impl YouTubeService {
+    pub fn with_cache(cache: MetadataCache, cache_changed_tx: mpsc::UnboundedSender<()>) -> Self {
+        Self {
+            client: reqwest::Client::builder()
+                .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36")
+                .build()
+                .unwrap_or_default(),
+            cache: Mutex::new(cache),
+            cache_changed_tx,
+        }
+    }
+
+    pub fn cache_stats(&self) -> CacheStats {
+        self.lock_cache().stats()
+    }
+
+    fn lock_cache(&self) -> MutexGuard<'_, MetadataCache> {
+        // 다른 스레드가 캐시를 쓰다 패닉해도 캐시는 계속 사용
+        self.cache
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    // 캐시를 바꾸고 저장 요청 (파일은 나중에 한 번에 저장)
+    fn update_cache(&self, update: impl FnOnce(&mut MetadataCache, i64)) {
+        update(&mut self.lock_cache(), chrono::Utc::now().timestamp());
+        let _ = self.cache_changed_tx.send(());
+    }
+
+    // 바뀐 캐시를 파일에 저장 (파일을 쓰는 동안에는 캐시를 잠그지 않음)
+    pub fn save_cache(&self) -> Result<(), String> {
+        let changes = self.lock_cache().take_changes()?;
+        let Some((path, content)) = changes else {
+            return Ok(());
+        };
+        cache::write_file(&path, &content).inspect_err(|_| self.lock_cache().mark_dirty())
+    }
+
+    // Search YouTube using the search page
+    pub async fn search(&self, query: &str, limit: usize) -> Result<YouTubeSearchResult, String> {
+        let now = chrono::Utc::now().timestamp();
+        let cached = self.lock_cache().search(query, limit, now);
+        if let Some(videos) = cached {
+            return Ok(YouTubeSearchResult { videos });
+        }
+
+        let encoded_query = urlencoding::encode(query);
+        let search_url = format!(
+            "https://www.youtube.com/results?search_query={}",
+            encoded_query
+        );
+
+        let response = self
+            .client
+            .get(&search_url)
+            .send()
+            .await
+            .map_err(|e| format!("Failed to search YouTube: {}", e))?;
+
+        let html = response
+            .text()
+            .await
+            .map_err(|e| format!("Failed to read response: {}", e))?;
+
+        let videos = parse_search_results(&html, limit)?;
+        self.update_cache(|cache, now| cache.insert_search(query, &videos, limit, now));
+
+        Ok(YouTubeSearchResult { videos })
+    }
+
+    // 재생목록 페이지에서 영상 목록을 가져옴 (최대 limit개)
+    pub async fn get_playlist_videos(
+        &self,
+        list_id: &str,
+        limit: usize,
+    ) -> Result<Vec<YouTubeVideo>, String> {
+        let playlist_url = format!(
+            "https://www.youtube.com/playlist?list={}",
+            urlencoding::encode(list_id)
+        );
+
+        let html = self
+            .client
+            .get(&playlist_url)
+            .send()
+            .await
+            .map_err(|e| format!("Failed to fetch playlist: {}", e))?
+            .text()
+            .await
+            .map_err(|e| format!("Failed to read response: {}", e))?;
+
+        let videos = parse_playlist_page(&html, limit)?;
+        if videos.is_empty() {
+            return Err("재생목록에 재생할 수 있는 영상이 없습니다".to_string());
+        }
+        Ok(videos)
+    }
+
+    // Alternative method using oembed API (more reliable but limited info)
+    pub async fn get_video_info_oembed(&self, video_id: &str) -> Result<YouTubeVideo, String> {
+        let now = chrono::Utc::now().timestamp();
+        let cached = self.lock_cache().video(video_id, now);
+        if let Some(video) = cached {
+            return Ok(video);
+        }
+
+        let oembed_url = format!(
+            "https://www.youtube.com/oembed?url=https://www.youtube.com/watch?v={}&format=json",
+            video_id
+        );
+
+        let response = self
+            .client
+            .get(&oembed_url)
+            .send()
+            .await
+            .map_err(|e| format!("Failed to fetch oembed data: {}", e))?;
+
+        let oembed_data: serde_json::Value = response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse oembed data: {}", e))?;
+
+        let title = oembed_data["title"]
+            .as_str()
+            .unwrap_or("Unknown Title")
+            .to_string();
+
+        let channel = oembed_data["author_name"]
+            .as_str()
+            .unwrap_or("Unknown Channel")
+            .to_string();
+
+        let thumbnail = oembed_data["thumbnail_url"].as_str().map(|s| s.to_string());
+
+        let video = YouTubeVideo {
+            video_id: video_id.to_string(),
+            title,
+            channel,
+            duration: None, // oembed doesn't provide duration
+            thumbnail,
+            url: format!("https://www.youtube.com/watch?v={}", video_id),
+            metadata: None,
+            start_seconds: None,
+        };
+        self.update_cache(|cache, now| cache.insert_video(&video, now));
+        Ok(video)
+    }
+
+    // 영상 페이지를 받아 길이와 재생 가능 여부를 확인
+    pub async fn get_video_metadata(&self, video_id: &str) -> Result<VideoMetadata, String> {
+        let now = chrono::Utc::now().timestamp();
+        let cached = self.lock_cache().metadata(video_id, now);
+        if let Some(metadata) = cached {
+            return Ok(metadata);
+        }
+
+        let watch_url = format!(
+            "https://www.youtube.com/watch?v={}&hl=ko",
+            urlencoding::encode(video_id)
+        );
+
+        let html = self
+            .client
+            .get(&watch_url)
+            .send()
+            .await
+            .map_err(|e| format!("Failed to fetch video page: {}", e))?
+            .text()
+            .await
+            .map_err(|e| format!("Failed to read response: {}", e))?;
+
+        let metadata = parse_watch_page(&html)?;
+        self.update_cache(|cache, now| cache.set_metadata(video_id, metadata.clone(), now));
+        Ok(metadata)
+    }
+}
+
+// 페이지에 `var 이름 = {...};` 형태로 들어 있는 JSON (뒤에 이어지는 스크립트는 무시)
+fn embedded_json(html: &str, name: &str) -> Option<serde_json::Value> {
+    let marker = format!("var {} = ", name);
+    let start = html.find(&marker)? + marker.len();
+    serde_json::Deserializer::from_str(&html[start..])
+        .into_iter::<serde_json::Value>()
+        .next()?
+        .ok()
+}
+
+fn initial_data(html: &str) -> Option<serde_json::Value> {
+    embedded_json(html, "ytInitialData")
+}
+
+// 광고와 추천 묶음(shelf) 안의 영상은 검색 결과가 아니므로 들어가지 않음
+const SKIPPED_RENDERERS: [&str; 6] = [
+    "adSlotRenderer",
+    "promotedSparklesWebRenderer",
+    "shelfRenderer",
+    "reelShelfRenderer",
+    "richShelfRenderer",
+    "horizontalCardListRenderer",
+];
+
+// JSON 트리에서 주어진 키를 가진 객체를 배열 순서대로 모음
+fn collect_renderers<'a>(
+    value: &'a serde_json::Value,
+    key: &str,
+    found: &mut Vec<&'a serde_json::Value>,
+) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (name, child) in map {
+                if name == key {
+                    found.push(child);
+                } else if !SKIPPED_RENDERERS.contains(&name.as_str()) {
+                    collect_renderers(child, key, found);
+                }
+            }
+        }
+        serde_json::Value::Array(values) => {
+            for child in values {
+                collect_renderers(child, key, found);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn first_run_text(value: &serde_json::Value) -> Option<String> {
+    value["runs"][0]["text"]
+        .as_str()
+        .or_else(|| value["simpleText"].as_str())
+        .map(str::to_string)
+}
+
+// videoRenderer/playlistVideoRenderer 하나를 영상 정보로 변환 (채널 이름 위치는 페이지마다 다름)
+fn video_from_renderer(renderer: &serde_json::Value, byline_keys: &[&str]) -> Option<YouTubeVideo> {
+    let video_id = renderer["videoId"].as_str()?.to_string();
+    Some(YouTubeVideo {
+        title: first_run_text(&renderer["title"]).unwrap_or_else(|| "Unknown Title".to_string()),
+        channel: byline_keys
+            .iter()
+            .find_map(|key| first_run_text(&renderer[*key]))
+            .unwrap_or_else(|| "Unknown Channel".to_string()),
+        // 라이브 방송은 길이가 없음
+        duration: renderer["lengthText"]["simpleText"]
+            .as_str()
+            .map(str::to_string),
+        thumbnail: renderer["thumbnail"]["thumbnails"][0]["url"]
+            .as_str()
+            .map(str::to_string),
+        url: format!("https://www.youtube.com/watch?v={}", video_id),
+        video_id,
+        metadata: None,
+        start_seconds: None,
+    })
+}
+
+// 검색 결과 페이지의 videoRenderer 항목 (채널, 재생목록, 광고, 추천 묶음은 제외)
+fn parse_search_results(html: &str, limit: usize) -> Result<Vec<YouTubeVideo>, String> {
+    let data = initial_data(html).ok_or_else(|| "Failed to parse search results".to_string())?;
+    let mut renderers = Vec::new();
+    collect_renderers(&data, "videoRenderer", &mut renderers);
+
+    let videos: Vec<YouTubeVideo> = renderers
+        .into_iter()
+        .filter_map(|renderer| video_from_renderer(renderer, &["longBylineText", "ownerText"]))
+        .take(limit)
+        .collect();
+
+    if videos.is_empty() {
+        Err("No videos found".to_string())
+    } else {
+        Ok(videos)
+    }
+}
+
+// 재생목록 페이지의 playlistVideoRenderer 항목 (삭제/비공개 등 재생할 수 없는 영상은 제외)
+fn parse_playlist_page(html: &str, limit: usize) -> Result<Vec<YouTubeVideo>, String> {
+    let data = initial_data(html).ok_or_else(|| "Failed to parse playlist page".to_string())?;
+    let mut renderers = Vec::new();
+    collect_renderers(&data, "playlistVideoRenderer", &mut renderers);
+
+    let videos = renderers
+        .into_iter()
+        .filter(|renderer| renderer["isPlayable"].as_bool() != Some(false))
+        .filter_map(|renderer| video_from_renderer(renderer, &["shortBylineText"]))
+        .take(limit)
+        .collect();
+    Ok(videos)
+}
+
+// 영상 페이지의 ytInitialPlayerResponse에서 재생 정보를 읽음
+fn parse_watch_page(html: &str) -> Result<VideoMetadata, String> {
+    let player = embedded_json(html, "ytInitialPlayerResponse")
+        .ok_or_else(|| "Failed to parse video page".to_string())?;
+    let details = &player["videoDetails"];
+    let status = &player["playabilityStatus"];
+    let status_code = status["status"].as_str().unwrap_or("ERROR");
+
+    let is_live = details["isLive"].as_bool().unwrap_or(false);
+    let is_upcoming =
+        details["isUpcoming"].as_bool().unwrap_or(false) || status_code == "LIVE_STREAM_OFFLINE";
+    let duration_secs = details["lengthSeconds"]
+        .as_str()
+        .and_then(|secs| secs.parse::<u64>().ok())
+        .filter(|&secs| secs > 0 && !is_live && !is_upcoming);
+
+    // 연령 제한 영상은 로그인이 필요하다고 나오므로 상태 코드만으로는 구분되지 않음
+    // (isFamilySafe가 false여도 재생 가능한 영상이 많아서 보지 않음)
+    let age_restricted = matches!(
+        status_code,
+        "AGE_CHECK_REQUIRED" | "AGE_VERIFICATION_REQUIRED"
+    ) || status.get("desktopLegacyAgeGateReason").is_some();
+
+    let available_countries = player["microformat"]["playerMicroformatRenderer"]
+        ["availableCountries"]
+        .as_array()
+        .map(|countries| {
+            countries
+                .iter()
+                .filter_map(|country| country.as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let unavailable_reason = match status_code {
+        "OK" | "LIVE_STREAM_OFFLINE" => None,
+        _ => Some(
+            status["reason"]
+                .as_str()
+                .map(str::to_string)
+                .unwrap_or_else(|| "재생할 수 없는 영상입니다".to_string()),
+        ),
+    };
+
+    Ok(VideoMetadata {
+        duration_secs,
+        is_live,
+        is_upcoming,
+        // 값이 빠진 경우가 있어서 명시적으로 막힌 경우만 외부 재생 불가로 봄
+        embeddable: status["playableInEmbed"].as_bool() != Some(false),
+        age_restricted,
+        available_countries,
+        unavailable_reason,
+    })
+}
+
+// 테스트에서 같이 쓰는 영상 정보
+#[cfg(test)]
+pub(crate) fn test_video(id: &str) -> YouTubeVideo {
+    YouTubeVideo {
+        video_id: id.to_string(),
+        title: format!("Song {}", id),
+        channel: "Channel".to_string(),
+        duration: None,
+        thumbnail: None,
+        url: format!("https://youtu.be/{}", id),
+        metadata: None,
+        start_seconds: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SEARCH_RESULTS: &str = include_str!("../../tests/fixtures/youtube_search_results.html");
+    const SEARCH_NO_RESULTS: &str =
+        include_str!("../../tests/fixtures/youtube_search_no_results.html");
+
+    #[test]
+    fn test_parse_search_results() {
+        let videos = parse_search_results(SEARCH_RESULTS, 10).unwrap();
+        let ids: Vec<&str> = videos.iter().map(|v| v.video_id.as_str()).collect();
+        // 광고, 추천 묶음, 채널, 재생목록은 제외
+        assert_eq!(ids, ["v8OJnHsXhjE", "TgOu00Mf3kI", "jfKfPfyJRdk"]);
+
+        // 영상마다 자기 정보를 가짐
+        assert_eq!(videos[0].title, "Rust in 100 Seconds");
+        assert_eq!(videos[0].channel, "Fireship");
+        assert_eq!(videos[0].duration.as_deref(), Some("2:29"));
+        assert_eq!(
+            videos[0].thumbnail.as_deref(),
+            Some("https://i.ytimg.com/vi/v8OJnHsXhjE/hq720.jpg")
+        );
+        assert_eq!(videos[1].title, "아이유 - 밤편지 & 라일락");
+        assert_eq!(videos[1].channel, "이지금 [IU Official]");
+        assert_eq!(videos[1].duration.as_deref(), Some("1:02:05"));
+        assert_eq!(videos[1].url, "https://www.youtube.com/watch?v=TgOu00Mf3kI");
+        // 라이브 방송은 길이가 없고, 채널은 ownerText에서 가져옴
+        assert_eq!(videos[2].duration, None);
+        assert_eq!(videos[2].channel, "Lofi Girl");
+
+        assert_eq!(parse_search_results(SEARCH_RESULTS, 1).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_unicode_decode() {
+        // 페이지에 \uXXXX로 들어 있는 문자는 JSON을 읽을 때 풀림
+        let html = r#"<script>var ytInitialData = {"title": "Test \u0048\u0065\u006c\u006c\u006f"};</script>"#;
+        assert_eq!(initial_data(html).unwrap()["title"], "Test Hello");
+
+        let videos = parse_search_results(SEARCH_RESULTS, 10).unwrap();
+        assert_eq!(videos[1].title, "아이유 - 밤편지 & 라일락");
+    }
+
+    #[test]
+    fn test_parse_search_results_without_videos() {
+        assert_eq!(
+            parse_search_results(SEARCH_NO_RESULTS, 10).unwrap_err(),
+            "No videos found"
+        );
+        assert!(parse_search_results("<html></html>", 10).is_err());
+    }
+
+    const WATCH_PAGE: &str = include_str!("../../tests/fixtures/youtube_watch_page.html");
+    const WATCH_PAGE_NOT_FAMILY_SAFE: &str =
+        include_str!("../../tests/fixtures/youtube_watch_page_not_family_safe.html");
+
+    fn watch_page(player_response: &str) -> String {
+        format!(
+            "<script>var ytInitialPlayerResponse = {};</script>",
+            player_response
+        )
+    }
+
+    #[test]
+    fn test_parse_watch_page() {
+        let metadata = parse_watch_page(WATCH_PAGE).unwrap();
+        assert_eq!(
+            metadata,
+            VideoMetadata {
+                duration_secs: Some(149),
+                embeddable: true,
+                available_countries: vec!["KR".to_string(), "US".to_string(), "JP".to_string()],
+                ..VideoMetadata::default()
+            }
+        );
+
+        let live = parse_watch_page(&watch_page(
+            r#"{"playabilityStatus":{"status":"OK","playableInEmbed":true},
+                "videoDetails":{"lengthSeconds":"0","isLive":true,"isLiveContent":true}}"#,
+        ))
+        .unwrap();
+        assert!(live.is_live);
+        assert_eq!(live.duration_secs, None);
+
+        let upcoming = parse_watch_page(&watch_page(
+            r#"{"playabilityStatus":{"status":"LIVE_STREAM_OFFLINE","reason":"Premieres soon","playableInEmbed":true},
+                "videoDetails":{"lengthSeconds":"0","isUpcoming":true}}"#,
+        ))
+        .unwrap();
+        assert!(upcoming.is_upcoming);
+        assert_eq!(upcoming.unavailable_reason, None);
+        assert!(parse_watch_page("<html></html>").is_err());
+    }
+
+    #[test]
+    fn test_parse_watch_page_not_family_safe() {
+        // 가족용이 아니어도 연령 제한이 없으면 재생 가능, playableInEmbed가 없어도 외부 재생 가능
+        let metadata = parse_watch_page(WATCH_PAGE_NOT_FAMILY_SAFE).unwrap();
+        assert!(!metadata.age_restricted);
+        assert!(metadata.embeddable);
+        assert_eq!(metadata.duration_secs, Some(151));
+        assert_eq!(metadata.unavailable_reason, None);
+        assert!(!metadata.is_blocked_in("KR"));
+    }
+
+    #[test]
+    fn test_parse_watch_page_restrictions() {
+        let not_embeddable = parse_watch_page(&watch_page(
+            r#"{"playabilityStatus":{"status":"OK","playableInEmbed":false},
+                "videoDetails":{"lengthSeconds":"200"}}"#,
+        ))
+        .unwrap();
+        assert!(!not_embeddable.embeddable);
+        assert_eq!(not_embeddable.duration_secs, Some(200));
+
+        let age_restricted = parse_watch_page(&watch_page(
+            r#"{"playabilityStatus":{"status":"LOGIN_REQUIRED","reason":"Sign in to confirm your age","desktopLegacyAgeGateReason":1},
+                "videoDetails":{"lengthSeconds":"200"}}"#,
+        ))
+        .unwrap();
+        assert!(age_restricted.age_restricted);
+
+        let region_blocked = parse_watch_page(&watch_page(
+            r#"{"playabilityStatus":{"status":"UNPLAYABLE","reason":"The uploader has not made this video available in your country"},
+                "videoDetails":{"lengthSeconds":"200"},
+                "microformat":{"playerMicroformatRenderer":{"availableCountries":["US","CA"]}}}"#,
+        ))
+        .unwrap();
+        assert!(region_blocked.is_blocked_in("KR"));
+        assert!(!region_blocked.is_blocked_in("us"));
+        assert!(region_blocked.unavailable_reason.is_some());
+
+        let removed = parse_watch_page(&watch_page(
+            r#"{"playabilityStatus":{"status":"ERROR","reason":"Video unavailable"}}"#,
+        ))
+        .unwrap();
+        assert_eq!(
+            removed.unavailable_reason.as_deref(),
+            Some("Video unavailable")
+        );
+        assert_eq!(removed.duration_secs, None);
+    }
+
+    #[test]
+    fn test_parse_playlist_page() {
+        let html = r#"<script>var ytInitialData = {"contents":{"list":[
+            {"playlistVideoRenderer":{"videoId":"aaa","title":{"runs":[{"text":"First"}]},
+              "shortBylineText":{"runs":[{"text":"Channel A"}]},"lengthText":{"simpleText":"3:45"},
+              "thumbnail":{"thumbnails":[{"url":"https://i.ytimg.com/vi/aaa/default.jpg"}]}}},
+            {"playlistVideoRenderer":{"videoId":"bbb","title":{"runs":[{"text":"[Private video]"}]},"isPlayable":false}},
+            {"playlistVideoRenderer":{"videoId":"ccc","title":{"simpleText":"Third"}}}
+        ]}};</script>"#;
+
+        let videos = parse_playlist_page(html, 10).unwrap();
+        assert_eq!(videos.len(), 2);
+        assert_eq!(videos[0].title, "First");
+        assert_eq!(videos[0].channel, "Channel A");
+        assert_eq!(videos[0].duration.as_deref(), Some("3:45"));
+        assert_eq!(videos[1].video_id, "ccc");
+        assert_eq!(videos[1].title, "Third");
+
+        assert_eq!(parse_playlist_page(html, 1).unwrap().len(), 1);
+        assert!(parse_playlist_page("<html></html>", 10).is_err());
+    }
+}
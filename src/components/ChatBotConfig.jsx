@@ -0,0 +1,122 @@
+import { memo, useState, useEffect, useCallback } from "react";
+import { invoke } from "@tauri-apps/api/core";
+
+const ChatBotConfig = memo(() => {
+    const [config, setConfig] = useState(null);
+    const [nidAut, setNidAut] = useState("");
+    const [nidSes, setNidSes] = useState("");
+    const [authConfigured, setAuthConfigured] = useState(false);
+    const [message, setMessage] = useState("");
+
+    useEffect(() => {
+        const loadConfig = async () => {
+            try {
+                // 로그인 쿠키는 받아오지 않고 저장 여부만 확인
+                const { auth_configured, ...chzzkConfig } =
+                    await invoke("get_chzzk_config");
+                setConfig(chzzkConfig);
+                setAuthConfigured(auth_configured);
+            } catch (error) {
+                console.error("Failed to load chzzk config:", error);
+            }
+        };
+        loadConfig();
+    }, []);
+
+    const showMessage = useCallback((text) => {
+        setMessage(text);
+        setTimeout(() => setMessage(""), 3000);
+    }, []);
+
+    const saveConfig = useCallback(
+        async (newConfig) => {
+            try {
+                await invoke("update_chzzk_config", { config: newConfig });
+                setConfig(newConfig);
+                showMessage("저장되었습니다.");
+            } catch (error) {
+                console.error("Failed to save chzzk config:", error);
+                showMessage("설정 저장에 실패했습니다.");
+            }
+        },
+        [showMessage],
+    );
+
+    const saveAuth = useCallback(async () => {
+        const auth =
+            nidAut.trim() && nidSes.trim()
+                ? { nid_aut: nidAut.trim(), nid_ses: nidSes.trim() }
+                : null;
+        try {
+            await invoke("update_chzzk_auth", { auth });
+            setAuthConfigured(auth !== null);
+            setNidAut("");
+            setNidSes("");
+            showMessage("저장되었습니다. 다음 연결부터 적용됩니다.");
+        } catch (error) {
+            console.error("Failed to save chzzk auth:", error);
+            showMessage("로그인 정보 저장에 실패했습니다.");
+        }
+    }, [nidAut, nidSes, showMessage]);
+
+    if (!config) {
+        return null;
+    }
+
+    return (
+        <div className="config-section">
+            <h3>채팅 봇</h3>
+
+            <div className="custom-command-form">
+                <input
+                    type="password"
+                    value={nidAut}
+                    onChange={(e) => setNidAut(e.target.value)}
+                    placeholder="NID_AUT 쿠키"
+                />
+                <input
+                    type="password"
+                    value={nidSes}
+                    onChange={(e) => setNidSes(e.target.value)}
+                    placeholder="NID_SES 쿠키"
+                />
+                <div className="custom-command-actions">
+                    <button className="confirm-alias" onClick={saveAuth}>
+                        로그인 정보 저장
+                    </button>
+                </div>
+                <p className="limit-description">
+                    {authConfigured
+                        ? "로그인 정보가 저장되어 있습니다. 비워두고 저장하면 지웁니다."
+                        : "네이버 로그인 쿠키가 있어야 채팅을 보낼 수 있습니다. 비워두면 읽기 전용으로 연결합니다."}
+                </p>
+            </div>
+
+            <label className="permission-months">
+                <input
+                    type="checkbox"
+                    checked={config.reply_to_chat}
+                    onChange={() =>
+                        saveConfig({
+                            ...config,
+                            reply_to_chat: !config.reply_to_chat,
+                        })
+                    }
+                />
+                명령어 처리 결과를 채팅으로 답장
+            </label>
+
+            {message && (
+                <div
+                    className={`config-message ${message.includes("실패") ? "error" : "success"}`}
+                >
+                    {message}
+                </div>
+            )}
+        </div>
+    );
+});
+
+ChatBotConfig.displayName = "ChatBotConfig";
+
+export default ChatBotConfig;
@@ -7,6 +7,7 @@ import "./ChzzkChat.css";
 const ActionTypes = {
     SET_CHANNEL_ID: "SET_CHANNEL_ID",
     SET_CONNECTED: "SET_CONNECTED",
+    SET_WATCH_MODE: "SET_WATCH_MODE",
     ADD_MESSAGE: "ADD_MESSAGE",
     CLEAR_MESSAGES: "CLEAR_MESSAGES",
     SET_ERROR: "SET_ERROR",
@@ -18,6 +19,7 @@ const ActionTypes = {
 const initialState = {
     channelId: "",
     isConnected: false,
+    watchMode: false,
     messages: [],
     error: "",
 };
@@ -30,6 +32,7 @@ const createChatMessage = (event) => ({
     message: event.msg,
     time: new Date(event.msgTime),
     profile: event.profile,
+    badges: event.badges,
 });
 
 const createDonationMessage = (event) => ({
@@ -63,6 +66,43 @@ const createDisconnectedMessage = () => ({
     time: new Date(),
 });
 
+const createReconnectingMessage = (event) => ({
+    type: "system",
+    id: `reconnecting-${Date.now()}`,
+    message: `채팅 연결이 끊어져 재연결을 시도합니다... (${event.attempt}번째, ${Math.round(event.delayMs / 1000)}초 후)`,
+    time: new Date(),
+});
+
+const createCommandRejectedMessage = (event, reason) => ({
+    type: "system",
+    id: `command-rejected-${Date.now()}`,
+    message: `${event.username}님의 ${event.command} 명령어: ${reason}`,
+    time: new Date(),
+});
+
+const createReconnectedMessage = () => ({
+    type: "system",
+    id: `reconnected-${Date.now()}`,
+    message: "채팅에 다시 연결되었습니다.",
+    time: new Date(),
+});
+
+const createStreamStartedMessage = (event) => ({
+    type: "system",
+    id: `stream-started-${Date.now()}`,
+    message: event.liveTitle
+        ? `방송이 시작되었습니다: ${event.liveTitle}`
+        : "방송이 시작되었습니다.",
+    time: new Date(),
+});
+
+const createStreamEndedMessage = () => ({
+    type: "system",
+    id: `stream-ended-${Date.now()}`,
+    message: "방송이 종료되었습니다. 다음 방송을 기다립니다.",
+    time: new Date(),
+});
+
 // 메시지 중복 체크
 const isDuplicateMessage = (messages, messageId) =>
     messages.some((msg) => msg.id === messageId);
@@ -76,6 +116,9 @@ const chatReducer = (state, action) => {
         case ActionTypes.SET_CONNECTED:
             return { ...state, isConnected: action.payload };
 
+        case ActionTypes.SET_WATCH_MODE:
+            return { ...state, watchMode: action.payload };
+
         case ActionTypes.ADD_MESSAGE:
             if (isDuplicateMessage(state.messages, action.payload.id)) {
                 console.log(
@@ -119,6 +162,20 @@ const formatTime = (date) =>
         second: "2-digit",
     });
 
+// 배지 요소 생성 (저장된 메시지는 역할 배지만 가지고 있음)
+const getBadgeElements = (msg) => {
+    if (msg.badges?.length) {
+        return msg.badges.map((badge) => ({
+            type: "badge",
+            src: badge.imageUrl,
+        }));
+    }
+
+    return msg.profile?.badge?.imageUrl
+        ? [{ type: "badge", src: msg.profile.badge.imageUrl }]
+        : [];
+};
+
 // 메시지 렌더링 데이터 생성
 const createMessageRenderData = (msg) => {
     switch (msg.type) {
@@ -127,10 +184,7 @@ const createMessageRenderData = (msg) => {
                 className: "chat-message",
                 elements: [
                     { type: "time", content: formatTime(msg.time) },
-                    msg.profile?.badge && {
-                        type: "badge",
-                        src: msg.profile.badge.imageUrl,
-                    },
+                    ...getBadgeElements(msg),
                     { type: "nickname", content: msg.nickname },
                     { type: "separator", content: ":" },
                     { type: "message", content: msg.message },
@@ -262,35 +316,6 @@ const useEventHandlers = (dispatch) => {
                     err,
                 );
             }
-
-            // AI 분석 및 명령어 처리를 위해 백엔드로 메시지 전송
-            try {
-                console.log("[ChzzkChat] Sending to backend:", {
-                    username: event.nickname,
-                    message: event.msg,
-                    isCommand: event.msg.startsWith("!"),
-                });
-
-                await invoke("add_chat_message", {
-                    username: event.nickname,
-                    message: event.msg,
-                });
-            } catch (err) {
-                console.error("Failed to send message to backend:", err);
-                // 명령어 처리 실패 시 시스템 메시지로 알림
-                if (event.msg.startsWith("!")) {
-                    const errorMessage = {
-                        type: "system",
-                        id: `error-${Date.now()}`,
-                        message: `명령어 처리 중 오류가 발생했습니다: ${err}`,
-                        time: new Date(),
-                    };
-                    dispatch({
-                        type: ActionTypes.ADD_MESSAGE,
-                        payload: errorMessage,
-                    });
-                }
-            }
         },
         [dispatch],
     );
@@ -320,21 +345,6 @@ const useEventHandlers = (dispatch) => {
                     err,
                 );
             }
-
-            // 후원 메시지도 AI 분석에 포함 (명령어는 제외)
-            if (event.msg && !event.msg.startsWith("!")) {
-                try {
-                    await invoke("add_chat_message", {
-                        username: event.nickname || "익명의 후원자",
-                        message: `[후원 ${event.extras.payAmount}원] ${event.msg}`,
-                    });
-                } catch (err) {
-                    console.error(
-                        "Failed to send donation message to backend:",
-                        err,
-                    );
-                }
-            }
         },
         [dispatch],
     );
@@ -432,6 +442,34 @@ const useEventHandlers = (dispatch) => {
         }
     }, [dispatch]);
 
+    const handleStatusEvent = useCallback(
+        async (message) => {
+            dispatch({ type: ActionTypes.ADD_MESSAGE, payload: message });
+
+            // Store status message in backend
+            try {
+                await invoke("store_display_message", {
+                    message: {
+                        id: message.id,
+                        message_type: "system",
+                        username: null,
+                        message: message.message,
+                        timestamp: message.time.getTime(),
+                        profile_image: null,
+                        badge_url: null,
+                        donation_amount: null,
+                    },
+                });
+            } catch (err) {
+                console.error(
+                    "[ChzzkChat] Failed to store status message:",
+                    err,
+                );
+            }
+        },
+        [dispatch],
+    );
+
     const handleErrorEvent = useCallback(
         (message) => {
             dispatch({ type: ActionTypes.SET_ERROR, payload: message });
@@ -445,6 +483,7 @@ const useEventHandlers = (dispatch) => {
         handleSystemMessageEvent,
         handleConnectedEvent,
         handleDisconnectedEvent,
+        handleStatusEvent,
         handleErrorEvent,
     };
 };
@@ -470,7 +509,7 @@ function ChzzkChat() {
                     connectionState,
                 });
 
-                if (isConnected) {
+                if (isConnected || connectionState === "watching") {
                     dispatch({
                         type: ActionTypes.SET_CONNECTED,
                         payload: true,
@@ -592,6 +631,26 @@ function ChzzkChat() {
                             case "disconnected":
                                 eventHandlers.handleDisconnectedEvent();
                                 break;
+                            case "reconnecting":
+                                eventHandlers.handleStatusEvent(
+                                    createReconnectingMessage(chatEvent),
+                                );
+                                break;
+                            case "reconnected":
+                                eventHandlers.handleStatusEvent(
+                                    createReconnectedMessage(),
+                                );
+                                break;
+                            case "streamStarted":
+                                eventHandlers.handleStatusEvent(
+                                    createStreamStartedMessage(chatEvent),
+                                );
+                                break;
+                            case "streamEnded":
+                                eventHandlers.handleStatusEvent(
+                                    createStreamEndedMessage(),
+                                );
+                                break;
                             case "error":
                                 eventHandlers.handleErrorEvent(
                                     chatEvent.message,
@@ -620,6 +679,46 @@ function ChzzkChat() {
         };
     }, [eventHandlers]);
 
+    // 거부된 명령어와 사용자 정의 명령어 응답 표시
+    useEffect(() => {
+        const unlistenDenied = listen("command:permission-denied", (event) => {
+            dispatch({
+                type: ActionTypes.ADD_MESSAGE,
+                payload: createCommandRejectedMessage(
+                    event.payload,
+                    "권한이 없습니다",
+                ),
+            });
+        });
+        const unlistenCooldown = listen("command:cooldown", (event) => {
+            dispatch({
+                type: ActionTypes.ADD_MESSAGE,
+                payload: createCommandRejectedMessage(
+                    event.payload,
+                    `대기시간 중 (${event.payload.remainingSecs}초)`,
+                ),
+            });
+        });
+
+        const unlistenResponse = listen("command:response", (event) => {
+            dispatch({
+                type: ActionTypes.ADD_MESSAGE,
+                payload: {
+                    type: "system",
+                    id: `command-response-${Date.now()}`,
+                    message: event.payload.response,
+                    time: new Date(),
+                },
+            });
+        });
+
+        return async () => {
+            (await unlistenDenied)();
+            (await unlistenCooldown)();
+            (await unlistenResponse)();
+        };
+    }, []);
+
     // Don't clear messages when component unmounts, preserve them
     useEffect(() => {
         mountedRef.current = true;
@@ -662,14 +761,24 @@ function ChzzkChat() {
         dispatch({ type: ActionTypes.CLEAR_ERROR });
 
         try {
-            await invoke("connect_chzzk_chat", { channelId: trimmedChannelId });
+            if (state.watchMode) {
+                // 방송 시작을 기다렸다가 자동으로 연결
+                await invoke("watch_chzzk_channel", {
+                    channelId: trimmedChannelId,
+                });
+                dispatch({ type: ActionTypes.SET_CONNECTED, payload: true });
+            } else {
+                await invoke("connect_chzzk_chat", {
+                    channelId: trimmedChannelId,
+                });
+            }
         } catch (err) {
             dispatch({
                 type: ActionTypes.SET_ERROR,
                 payload: err.toString(),
             });
         }
-    }, [state.channelId]);
+    }, [state.channelId, state.watchMode]);
 
     const handleDisconnect = useCallback(async () => {
         try {
@@ -710,6 +819,20 @@ function ChzzkChat() {
                     onChange={handleChannelIdChange}
                     disabled={state.isConnected}
                 />
+                <label className="watch-mode-toggle">
+                    <input
+                        type="checkbox"
+                        checked={state.watchMode}
+                        onChange={(e) =>
+                            dispatch({
+                                type: ActionTypes.SET_WATCH_MODE,
+                                payload: e.target.checked,
+                            })
+                        }
+                        disabled={state.isConnected}
+                    />
+                    방송 대기
+                </label>
                 {!state.isConnected ? (
                     <button onClick={handleConnect} className="connect-btn">
                         연결
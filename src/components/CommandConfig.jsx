@@ -1,7 +1,19 @@
 import { memo, useState, useEffect, useCallback } from "react";
 import { invoke } from "@tauri-apps/api/core";
+import CustomCommands from "./CustomCommands";
+import ChatBotConfig from "./ChatBotConfig";
+import RequestPolicyConfig from "./RequestPolicyConfig";
 import "./CommandConfig.css";
 
+// 명령어 사용 권한 목록
+const PERMISSION_OPTIONS = [
+    { value: "everyone", label: "모든 시청자" },
+    { value: "follower", label: "팔로워 이상" },
+    { value: "subscriber", label: "구독자 이상" },
+    { value: "manager", label: "매니저 이상" },
+    { value: "streamer", label: "스트리머만" },
+];
+
 const CommandConfig = memo(() => {
     const [config, setConfig] = useState({
         prefix: "!",
@@ -78,6 +90,69 @@ const CommandConfig = memo(() => {
         }));
     }, []);
 
+    const updatePermission = useCallback((commandName, permission) => {
+        setConfig((prev) => ({
+            ...prev,
+            commands: {
+                ...prev.commands,
+                [commandName]: {
+                    ...prev.commands[commandName],
+                    permission,
+                },
+            },
+        }));
+    }, []);
+
+    const updateCooldown = useCallback((commandName, field, value) => {
+        if (value !== "" && !/^\d+$/.test(value)) return;
+
+        setConfig((prev) => {
+            const command = prev.commands[commandName];
+            return {
+                ...prev,
+                commands: {
+                    ...prev.commands,
+                    [commandName]: {
+                        ...command,
+                        cooldown: {
+                            global_secs: 0,
+                            user_secs: 0,
+                            bypass: null,
+                            ...command.cooldown,
+                            [field]: value === "" ? 0 : parseInt(value, 10),
+                        },
+                    },
+                },
+            };
+        });
+    }, []);
+
+    const toggleCooldownBypass = useCallback((commandName) => {
+        setConfig((prev) => {
+            const command = prev.commands[commandName];
+            const cooldown = {
+                global_secs: 0,
+                user_secs: 0,
+                ...command.cooldown,
+            };
+            return {
+                ...prev,
+                commands: {
+                    ...prev.commands,
+                    [commandName]: {
+                        ...command,
+                        cooldown: {
+                            ...cooldown,
+                            bypass: cooldown.bypass
+                                ? null
+                                : { level: "manager" },
+                        },
+                    },
+                },
+            };
+        });
+    }, []);
+
     const addAlias = useCallback(
         (commandName) => {
             if (!newAlias.trim()) return;
@@ -127,6 +202,7 @@ const CommandConfig = memo(() => {
         const newConfig = {
             ...config,
             playlist_limits: {
+                ...config.playlist_limits,
                 user_limit: userLimit === "" ? null : parseInt(userLimit, 10),
             },
         };
@@ -134,6 +210,71 @@ const CommandConfig = memo(() => {
         saveConfig(newConfig);
     }, [config, userLimit]);
 
+    const savePlaylistLimits = useCallback(
+        (playlistLimits) => {
+            const newConfig = { ...config, playlist_limits: playlistLimits };
+            setConfig(newConfig);
+            saveConfig(newConfig);
+        },
+        [config],
+    );
+
+    // 우선 재생 설정 변경 (save가 true면 바로 저장)
+    const updateRequestPriority = useCallback(
+        (patch, save = false) => {
+            const newConfig = {
+                ...config,
+                request_priority: { ...config.request_priority, ...patch },
+            };
+            setConfig(newConfig);
+            if (save) {
+                saveConfig(newConfig);
+            }
+        },
+        [config],
+    );
+
+    // 건너뛰기 투표 설정 변경 (save가 true면 바로 저장)
+    const updateVoteSkip = useCallback(
+        (patch, save = false) => {
+            const newConfig = {
+                ...config,
+                vote_skip: { ...config.vote_skip, ...patch },
+            };
+            setConfig(newConfig);
+            if (save) {
+                saveConfig(newConfig);
+            }
+        },
+        [config],
+    );
+
+    // 검색 결과 고르기 설정 변경 (save가 true면 바로 저장)
+    const updateSearchPick = useCallback(
+        (patch, save = false) => {
+            const newConfig = {
+                ...config,
+                search_pick: { ...config.search_pick, ...patch },
+            };
+            setConfig(newConfig);
+            if (save) {
+                saveConfig(newConfig);
+            }
+        },
+        [config],
+    );
+
+    const handleVoteSkipTypeChange = useCallback(
+        (type) => {
+            const threshold =
+                type === "count"
+                    ? { type, votes: 3 }
+                    : { type, percent: 30 };
+            updateVoteSkip({ threshold }, true);
+        },
+        [updateVoteSkip],
+    );
+
     if (loading) {
         return (
             <div className="command-config loading">
@@ -201,6 +342,104 @@ const CommandConfig = memo(() => {
                                 </div>
                             </div>
 
+                            <div className="command-permission">
+                                <span className="permission-label">권한:</span>
+                                <select
+                                    value={command.permission?.level || "everyone"}
+                                    onChange={(e) =>
+                                        updatePermission(key, {
+                                            level: e.target.value,
+                                            ...(e.target.value ===
+                                                "subscriber" && {
+                                                min_months: 0,
+                                            }),
+                                        })
+                                    }
+                                >
+                                    {PERMISSION_OPTIONS.map((option) => (
+                                        <option
+                                            key={option.value}
+                                            value={option.value}
+                                        >
+                                            {option.label}
+                                        </option>
+                                    ))}
+                                </select>
+                                {command.permission?.level === "subscriber" && (
+                                    <label className="permission-months">
+                                        <input
+                                            type="number"
+                                            min="0"
+                                            value={
+                                                command.permission.min_months ||
+                                                0
+                                            }
+                                            onChange={(e) =>
+                                                updatePermission(key, {
+                                                    level: "subscriber",
+                                                    min_months: Math.max(
+                                                        0,
+                                                        parseInt(
+                                                            e.target.value,
+                                                            10,
+                                                        ) || 0,
+                                                    ),
+                                                })
+                                            }
+                                        />
+                                        개월 이상
+                                    </label>
+                                )}
+                            </div>
+
+                            <div className="command-cooldown">
+                                <span className="permission-label">
+                                    대기시간:
+                                </span>
+                                <label className="permission-months">
+                                    전체
+                                    <input
+                                        type="text"
+                                        value={
+                                            command.cooldown?.global_secs || 0
+                                        }
+                                        onChange={(e) =>
+                                            updateCooldown(
+                                                key,
+                                                "global_secs",
+                                                e.target.value,
+                                            )
+                                        }
+                                    />
+                                    초
+                                </label>
+                                <label className="permission-months">
+                                    유저별
+                                    <input
+                                        type="text"
+                                        value={command.cooldown?.user_secs || 0}
+                                        onChange={(e) =>
+                                            updateCooldown(
+                                                key,
+                                                "user_secs",
+                                                e.target.value,
+                                            )
+                                        }
+                                    />
+                                    초
+                                </label>
+                                <label className="permission-months">
+                                    <input
+                                        type="checkbox"
+                                        checked={!!command.cooldown?.bypass}
+                                        onChange={() =>
+                                            toggleCooldownBypass(key)
+                                        }
+                                    />
+                                    매니저 이상 무시
+                                </label>
+                            </div>
+
                             <div className="command-aliases">
                                 <span className="aliases-label">별칭:</span>
                                 <div className="aliases-list">
@@ -293,11 +532,324 @@ const CommandConfig = memo(() => {
                                     </p>
                                 </div>
                             )}
+
+                            {key === "playlist" && command.enabled && (
+                                <RequestPolicyConfig
+                                    limits={config.playlist_limits}
+                                    onSave={savePlaylistLimits}
+                                />
+                            )}
+
+                            {key === "playlist" &&
+                                command.enabled &&
+                                config.request_priority && (
+                                    <div className="user-limit-config">
+                                        <label className="limit-label">
+                                            우선 재생
+                                        </label>
+                                        <label className="permission-months">
+                                            <input
+                                                type="checkbox"
+                                                checked={
+                                                    config.request_priority
+                                                        .donation_enabled
+                                                }
+                                                onChange={() =>
+                                                    updateRequestPriority(
+                                                        {
+                                                            donation_enabled:
+                                                                !config
+                                                                    .request_priority
+                                                                    .donation_enabled,
+                                                        },
+                                                        true,
+                                                    )
+                                                }
+                                            />
+                                            후원 신청곡 우선 재생
+                                        </label>
+                                        {config.request_priority
+                                            .donation_enabled && (
+                                            <div className="limit-input-group">
+                                                <input
+                                                    type="number"
+                                                    min="0"
+                                                    value={
+                                                        config.request_priority
+                                                            .donation_min_amount
+                                                    }
+                                                    onChange={(e) =>
+                                                        updateRequestPriority({
+                                                            donation_min_amount:
+                                                                parseInt(
+                                                                    e.target
+                                                                        .value,
+                                                                    10,
+                                                                ) || 0,
+                                                        })
+                                                    }
+                                                    onBlur={() =>
+                                                        saveConfig(config)
+                                                    }
+                                                    className="limit-input"
+                                                />
+                                                <span className="limit-suffix">
+                                                    치즈 이상
+                                                </span>
+                                            </div>
+                                        )}
+                                        <label className="permission-months">
+                                            <input
+                                                type="checkbox"
+                                                checked={
+                                                    config.request_priority
+                                                        .subscriber_boost
+                                                }
+                                                onChange={() =>
+                                                    updateRequestPriority(
+                                                        {
+                                                            subscriber_boost:
+                                                                !config
+                                                                    .request_priority
+                                                                    .subscriber_boost,
+                                                        },
+                                                        true,
+                                                    )
+                                                }
+                                            />
+                                            구독자 신청곡을 일반 신청곡보다 먼저
+                                            재생
+                                        </label>
+                                        <p className="limit-description">
+                                            후원 메시지에 {config.prefix}
+                                            playlist 명령어나 YouTube 링크를
+                                            넣으면 신청곡으로 추가됩니다.
+                                        </p>
+                                    </div>
+                                )}
+
+                            {key === "pick" &&
+                                command.enabled &&
+                                config.search_pick && (
+                                    <div className="user-limit-config">
+                                        <label className="limit-label">
+                                            검색 결과 고르기
+                                        </label>
+                                        <label className="permission-months">
+                                            <input
+                                                type="checkbox"
+                                                checked={
+                                                    config.search_pick.enabled
+                                                }
+                                                onChange={() =>
+                                                    updateSearchPick(
+                                                        {
+                                                            enabled:
+                                                                !config
+                                                                    .search_pick
+                                                                    .enabled,
+                                                        },
+                                                        true,
+                                                    )
+                                                }
+                                            />
+                                            검색어 신청곡은 검색 결과 중에서
+                                            골라서 추가
+                                        </label>
+                                        {config.search_pick.enabled && (
+                                            <div className="limit-input-group">
+                                                <input
+                                                    type="number"
+                                                    min="1"
+                                                    max="10"
+                                                    value={
+                                                        config.search_pick
+                                                            .result_count
+                                                    }
+                                                    onChange={(e) =>
+                                                        updateSearchPick({
+                                                            result_count:
+                                                                Math.min(
+                                                                    parseInt(
+                                                                        e.target
+                                                                            .value,
+                                                                        10,
+                                                                    ) || 1,
+                                                                    10,
+                                                                ),
+                                                        })
+                                                    }
+                                                    onBlur={() =>
+                                                        saveConfig(config)
+                                                    }
+                                                    className="limit-input"
+                                                />
+                                                <span className="limit-suffix">
+                                                    개 중에서
+                                                </span>
+                                                <input
+                                                    type="number"
+                                                    min="10"
+                                                    value={
+                                                        config.search_pick
+                                                            .timeout_secs
+                                                    }
+                                                    onChange={(e) =>
+                                                        updateSearchPick({
+                                                            timeout_secs:
+                                                                parseInt(
+                                                                    e.target
+                                                                        .value,
+                                                                    10,
+                                                                ) || 10,
+                                                        })
+                                                    }
+                                                    onBlur={() =>
+                                                        saveConfig(config)
+                                                    }
+                                                    className="limit-input"
+                                                />
+                                                <span className="limit-suffix">
+                                                    초 안에 선택
+                                                </span>
+                                            </div>
+                                        )}
+                                        <p className="limit-description">
+                                            YouTube 링크로 신청한 곡은 바로
+                                            추가됩니다. 시청자는 {config.prefix}
+                                            pick 번호로 곡을 고릅니다.
+                                        </p>
+                                    </div>
+                                )}
+
+                            {key === "voteskip" &&
+                                command.enabled &&
+                                config.vote_skip && (
+                                    <div className="user-limit-config">
+                                        <label className="limit-label">
+                                            건너뛰기 기준
+                                            <span className="limit-hint">
+                                                (매니저 이상은 바로 건너뜀)
+                                            </span>
+                                        </label>
+                                        <div className="limit-input-group">
+                                            <select
+                                                value={
+                                                    config.vote_skip.threshold
+                                                        .type
+                                                }
+                                                onChange={(e) =>
+                                                    handleVoteSkipTypeChange(
+                                                        e.target.value,
+                                                    )
+                                                }
+                                            >
+                                                <option value="count">
+                                                    고정 표 수
+                                                </option>
+                                                <option value="percent">
+                                                    참여자 비율
+                                                </option>
+                                            </select>
+                                            {config.vote_skip.threshold.type ===
+                                            "count" ? (
+                                                <>
+                                                    <input
+                                                        type="number"
+                                                        min="1"
+                                                        value={
+                                                            config.vote_skip
+                                                                .threshold.votes
+                                                        }
+                                                        onChange={(e) =>
+                                                            updateVoteSkip({
+                                                                threshold: {
+                                                                    type: "count",
+                                                                    votes:
+                                                                        parseInt(
+                                                                            e
+                                                                                .target
+                                                                                .value,
+                                                                            10,
+                                                                        ) || 1,
+                                                                },
+                                                            })
+                                                        }
+                                                        onBlur={() =>
+                                                            saveConfig(config)
+                                                        }
+                                                        className="limit-input"
+                                                    />
+                                                    <span className="limit-suffix">
+                                                        표
+                                                    </span>
+                                                </>
+                                            ) : (
+                                                <>
+                                                    <input
+                                                        type="number"
+                                                        min="1"
+                                                        max="100"
+                                                        value={
+                                                            config.vote_skip
+                                                                .threshold
+                                                                .percent
+                                                        }
+                                                        onChange={(e) =>
+                                                            updateVoteSkip({
+                                                                threshold: {
+                                                                    type: "percent",
+                                                                    percent:
+                                                                        Math.min(
+                                                                            parseInt(
+                                                                                e
+                                                                                    .target
+                                                                                    .value,
+                                                                                10,
+                                                                            ) ||
+                                                                                1,
+                                                                            100,
+                                                                        ),
+                                                                },
+                                                            })
+                                                        }
+                                                        onBlur={() =>
+                                                            saveConfig(config)
+                                                        }
+                                                        className="limit-input"
+                                                    />
+                                                    <span className="limit-suffix">
+                                                        %
+                                                    </span>
+                                                </>
+                                            )}
+                                        </div>
+                                        {config.vote_skip.threshold.type ===
+                                            "percent" && (
+                                            <p className="limit-description">
+                                                최근{" "}
+                                                {Math.round(
+                                                    config.vote_skip
+                                                        .active_window_secs /
+                                                        60,
+                                                )}
+                                                분 동안 채팅한 시청자 기준이며,
+                                                최소{" "}
+                                                {config.vote_skip.min_votes}표가
+                                                필요합니다.
+                                            </p>
+                                        )}
+                                    </div>
+                                )}
                         </div>
                     ))}
                 </div>
             </div>
 
+            <CustomCommands prefix={config.prefix} />
+
+            <ChatBotConfig />
+
             <div className="config-section">
                 <h3>사용 예시</h3>
                 <div className="examples">
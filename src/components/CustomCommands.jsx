@@ -0,0 +1,214 @@
+import { memo, useState, useEffect, useCallback } from "react";
+import { invoke } from "@tauri-apps/api/core";
+
+const EMPTY_FORM = { name: "", aliases: "", response: "" };
+
+const TEMPLATE_VARIABLES = [
+    { name: "{user}", description: "명령어를 사용한 사람" },
+    { name: "{args}", description: "명령어 뒤에 입력한 내용" },
+    { name: "{uptime}", description: "방송 경과 시간" },
+    { name: "{viewers}", description: "현재 시청자 수" },
+    { name: "{song}", description: "재생 중인 곡" },
+    { name: "{count}", description: "명령어 사용 횟수" },
+];
+
+// 입력 폼을 명령어 데이터로 변환
+const formToCommand = (form, base) => ({
+    enabled: true,
+    permission: { level: "everyone" },
+    cooldown: { global_secs: 0, user_secs: 0, bypass: null },
+    count: 0,
+    ...base,
+    name: form.name.trim().toLowerCase(),
+    aliases: form.aliases
+        .split(",")
+        .map((alias) => alias.trim().toLowerCase())
+        .filter(Boolean),
+    response: form.response,
+});
+
+const CustomCommands = memo(({ prefix }) => {
+    const [commands, setCommands] = useState([]);
+    const [form, setForm] = useState(EMPTY_FORM);
+    const [editingName, setEditingName] = useState(null);
+    const [error, setError] = useState("");
+
+    const loadCommands = useCallback(async () => {
+        try {
+            setCommands(await invoke("get_custom_commands"));
+        } catch (err) {
+            console.error("Failed to load custom commands:", err);
+        }
+    }, []);
+
+    useEffect(() => {
+        loadCommands();
+    }, [loadCommands]);
+
+    const resetForm = useCallback(() => {
+        setForm(EMPTY_FORM);
+        setEditingName(null);
+        setError("");
+    }, []);
+
+    const handleSubmit = useCallback(async () => {
+        try {
+            if (editingName) {
+                const base = commands.find((c) => c.name === editingName);
+                await invoke("update_custom_command", {
+                    name: editingName,
+                    command: formToCommand(form, base),
+                });
+            } else {
+                await invoke("add_custom_command", {
+                    command: formToCommand(form),
+                });
+            }
+            resetForm();
+            await loadCommands();
+        } catch (err) {
+            setError(String(err));
+        }
+    }, [commands, editingName, form, loadCommands, resetForm]);
+
+    const handleEdit = useCallback((command) => {
+        setEditingName(command.name);
+        setForm({
+            name: command.name,
+            aliases: command.aliases.join(", "),
+            response: command.response,
+        });
+        setError("");
+    }, []);
+
+    const handleToggle = useCallback(
+        async (command) => {
+            try {
+                await invoke("update_custom_command", {
+                    name: command.name,
+                    command: { ...command, enabled: !command.enabled },
+                });
+                await loadCommands();
+            } catch (err) {
+                setError(String(err));
+            }
+        },
+        [loadCommands],
+    );
+
+    const handleRemove = useCallback(
+        async (name) => {
+            try {
+                await invoke("remove_custom_command", { name });
+                if (editingName === name) {
+                    resetForm();
+                }
+                await loadCommands();
+            } catch (err) {
+                setError(String(err));
+            }
+        },
+        [editingName, loadCommands, resetForm],
+    );
+
+    return (
+        <div className="config-section">
+            <h3>사용자 정의 명령어</h3>
+
+            <div className="commands-list">
+                {commands.map((command) => (
+                    <div key={command.name} className="command-item">
+                        <div className="command-header">
+                            <div className="command-info">
+                                <div className="command-name">
+                                    <label className="toggle-label">
+                                        <input
+                                            type="checkbox"
+                                            checked={command.enabled}
+                                            onChange={() =>
+                                                handleToggle(command)
+                                            }
+                                        />
+                                        <span className="toggle-slider"></span>
+                                    </label>
+                                    <span className="command-text">
+                                        {prefix}
+                                        {command.name}
+                                    </span>
+                                    <span className="custom-command-count">
+                                        {command.count}회 사용
+                                    </span>
+                                </div>
+                                <p className="command-description">
+                                    {command.response}
+                                </p>
+                            </div>
+                            <div className="custom-command-actions">
+                                <button
+                                    className="add-alias"
+                                    onClick={() => handleEdit(command)}
+                                >
+                                    수정
+                                </button>
+                                <button
+                                    className="add-alias"
+                                    onClick={() => handleRemove(command.name)}
+                                >
+                                    삭제
+                                </button>
+                            </div>
+                        </div>
+                    </div>
+                ))}
+            </div>
+
+            <div className="custom-command-form">
+                <input
+                    type="text"
+                    value={form.name}
+                    onChange={(e) => setForm({ ...form, name: e.target.value })}
+                    placeholder="명령어 이름 (예: discord)"
+                />
+                <input
+                    type="text"
+                    value={form.aliases}
+                    onChange={(e) =>
+                        setForm({ ...form, aliases: e.target.value })
+                    }
+                    placeholder="별칭 (쉼표로 구분)"
+                />
+                <textarea
+                    value={form.response}
+                    onChange={(e) =>
+                        setForm({ ...form, response: e.target.value })
+                    }
+                    placeholder="응답 (예: {user}님, 방송 시작한 지 {uptime} 지났어요!)"
+                    rows={2}
+                />
+                <div className="custom-command-actions">
+                    <button className="confirm-alias" onClick={handleSubmit}>
+                        {editingName ? "수정 완료" : "추가"}
+                    </button>
+                    {editingName && (
+                        <button className="cancel-alias" onClick={resetForm}>
+                            취소
+                        </button>
+                    )}
+                </div>
+                {error && <div className="config-message error">{error}</div>}
+            </div>
+
+            <div className="template-variables">
+                {TEMPLATE_VARIABLES.map((variable) => (
+                    <span key={variable.name} className="alias-tag">
+                        {variable.name} {variable.description}
+                    </span>
+                ))}
+            </div>
+        </div>
+    );
+});
+
+CustomCommands.displayName = "CustomCommands";
+
+export default CustomCommands;
@@ -0,0 +1,170 @@
+import { memo, useState, useEffect, useCallback } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import "./PlaylistInput.css";
+import "./FallbackPlaylist.css";
+
+// 신청곡이 없을 때 재생할 기본 재생목록 편집
+const FallbackPlaylist = memo(() => {
+    const [fallback, setFallback] = useState({
+        enabled: false,
+        shuffle: false,
+        items: [],
+    });
+    const [input, setInput] = useState("");
+    const [loading, setLoading] = useState(false);
+    const [message, setMessage] = useState("");
+
+    useEffect(() => {
+        loadFallback();
+    }, []);
+
+    const loadFallback = async () => {
+        try {
+            setFallback(await invoke("get_fallback_playlist"));
+        } catch (error) {
+            console.error("Failed to load fallback playlist:", error);
+        }
+    };
+
+    const showMessage = (text) => {
+        setMessage(text);
+        setTimeout(() => setMessage(""), 3000);
+    };
+
+    const saveFallback = useCallback(async (newFallback) => {
+        try {
+            await invoke("update_fallback_playlist", { fallback: newFallback });
+            setFallback(newFallback);
+        } catch (error) {
+            console.error("Failed to save fallback playlist:", error);
+            showMessage("설정 저장에 실패했습니다.");
+        }
+    }, []);
+
+    const handleAdd = useCallback(
+        async (e) => {
+            e.preventDefault();
+            const query = input.trim();
+            if (!query) return;
+
+            setLoading(true);
+            try {
+                setFallback(
+                    await invoke("add_to_fallback_playlist", { query }),
+                );
+                setInput("");
+            } catch (error) {
+                console.error("Failed to add to fallback playlist:", error);
+                showMessage(`추가 실패: ${error}`);
+            } finally {
+                setLoading(false);
+            }
+        },
+        [input],
+    );
+
+    const handleRemove = useCallback(
+        (index) => {
+            saveFallback({
+                ...fallback,
+                items: fallback.items.filter((_, i) => i !== index),
+            });
+        },
+        [fallback, saveFallback],
+    );
+
+    return (
+        <div className="playlist-input">
+            <div className="playlist-input-header">
+                <h3>기본 재생목록</h3>
+            </div>
+
+            {message && <div className="config-message error">{message}</div>}
+
+            <div className="playlist-command-config">
+                <div className="command-toggle">
+                    <label className="toggle-label">
+                        <input
+                            type="checkbox"
+                            checked={fallback.enabled}
+                            onChange={() =>
+                                saveFallback({
+                                    ...fallback,
+                                    enabled: !fallback.enabled,
+                                })
+                            }
+                        />
+                        <span className="toggle-slider"></span>
+                    </label>
+                    <span className="command-label">
+                        신청곡이 없을 때 기본 재생목록 재생
+                    </span>
+                </div>
+                <div className="command-toggle">
+                    <label className="toggle-label">
+                        <input
+                            type="checkbox"
+                            checked={fallback.shuffle}
+                            onChange={() =>
+                                saveFallback({
+                                    ...fallback,
+                                    shuffle: !fallback.shuffle,
+                                })
+                            }
+                        />
+                        <span className="toggle-slider"></span>
+                    </label>
+                    <span className="command-label">무작위 순서</span>
+                </div>
+
+                <form onSubmit={handleAdd} className="fallback-add-form">
+                    <input
+                        type="text"
+                        value={input}
+                        onChange={(e) => setInput(e.target.value)}
+                        placeholder="YouTube URL, 재생목록 URL 또는 검색어"
+                        disabled={loading}
+                    />
+                    <button type="submit" disabled={loading || !input.trim()}>
+                        {loading ? "⏳" : "추가"}
+                    </button>
+                </form>
+
+                {fallback.items.length === 0 ? (
+                    <p className="limit-description">
+                        기본 재생목록이 비어 있습니다.
+                    </p>
+                ) : (
+                    <ul className="fallback-items">
+                        {fallback.items.map((item, index) => (
+                            <li key={item.id} className="fallback-item">
+                                <span className="fallback-item-title">
+                                    {item.title}
+                                </span>
+                                <span className="fallback-item-channel">
+                                    {item.channel}
+                                </span>
+                                <button
+                                    className="fallback-item-remove"
+                                    onClick={() => handleRemove(index)}
+                                    title="삭제"
+                                >
+                                    ✕
+                                </button>
+                            </li>
+                        ))}
+                    </ul>
+                )}
+                <p className="limit-description">
+                    신청곡이 들어오면 기본 재생목록 곡은 바로 멈추고 신청곡이
+                    재생됩니다. 기본 재생목록 곡은 신청곡 제한에 포함되지
+                    않습니다.
+                </p>
+            </div>
+        </div>
+    );
+});
+
+FallbackPlaylist.displayName = "FallbackPlaylist";
+
+export default FallbackPlaylist;
@@ -1,18 +1,39 @@
-import { memo, useState, useEffect, useCallback } from "react";
+import { memo, useState, useEffect, useCallback, useRef } from "react";
 import { listen } from "@tauri-apps/api/event";
 import { invoke } from "@tauri-apps/api/core";
 import "./Playlist.css";
 
+const TRANSFER_FORMATS = [
+    { value: "json", label: "JSON", mime: "application/json" },
+    { value: "m3u", label: "M3U", mime: "audio/x-mpegurl" },
+    { value: "csv", label: "CSV", mime: "text/csv" },
+];
+
+// 파일 확장자로 가져오기 형식 결정
+const formatFromFileName = (name) => {
+    const extension = name.split(".").pop().toLowerCase();
+    if (extension === "m3u" || extension === "m3u8") return "m3u";
+    if (extension === "csv") return "csv";
+    return "json";
+};
+
 const Playlist = memo(() => {
     const [playlist, setPlaylist] = useState({
         items: [],
         current_index: null,
         is_playing: false,
         autoplay: true,
+        history: [],
+        remove_finished: false,
+        fair_queue: false,
+        pinned: [],
     });
+    const [showHistory, setShowHistory] = useState(false);
     const [draggedIndex, setDraggedIndex] = useState(null);
     const [dragOverIndex, setDragOverIndex] = useState(null);
     const [errorMessage, setErrorMessage] = useState("");
+    const [exportFormat, setExportFormat] = useState("json");
+    const importInputRef = useRef(null);
 
     // Load initial playlist
     useEffect(() => {
@@ -121,6 +142,94 @@ const Playlist = memo(() => {
         }
     }, []);
 
+    const handleRemoveFinishedToggle = useCallback(async () => {
+        try {
+            await invoke("set_remove_finished", {
+                enabled: !playlist.remove_finished,
+            });
+        } catch (error) {
+            console.error("Failed to update remove finished:", error);
+        }
+    }, [playlist.remove_finished]);
+
+    const handleFairQueueToggle = useCallback(async () => {
+        try {
+            await invoke("set_fair_queue", {
+                enabled: !playlist.fair_queue,
+            });
+        } catch (error) {
+            console.error("Failed to update fair queue:", error);
+        }
+    }, [playlist.fair_queue]);
+
+    const showError = useCallback((message) => {
+        setErrorMessage(message);
+        setTimeout(() => setErrorMessage(""), 5000);
+    }, []);
+
+    const handleExport = useCallback(async () => {
+        const format = TRANSFER_FORMATS.find((f) => f.value === exportFormat);
+        try {
+            const content = await invoke("export_playlist", {
+                format: format.value,
+            });
+            const url = URL.createObjectURL(
+                new Blob([content], { type: format.mime }),
+            );
+            const link = document.createElement("a");
+            link.href = url;
+            link.download = `playlist.${format.value}`;
+            link.click();
+            URL.revokeObjectURL(url);
+        } catch (error) {
+            console.error("Failed to export playlist:", error);
+            showError(`내보내기 실패: ${error}`);
+        }
+    }, [exportFormat, showError]);
+
+    const handleImportFile = useCallback(
+        async (e) => {
+            const file = e.target.files[0];
+            e.target.value = "";
+            if (!file) return;
+
+            try {
+                const content = await file.text();
+                const count = await invoke("import_playlist", {
+                    content,
+                    format: formatFromFileName(file.name),
+                });
+                console.log(`Imported ${count} items from ${file.name}`);
+            } catch (error) {
+                console.error("Failed to import playlist:", error);
+                showError(`가져오기 실패: ${error}`);
+            }
+        },
+        [showError],
+    );
+
+    const handleUndo = useCallback(
+        async (redo) => {
+            try {
+                await invoke(
+                    redo ? "redo_playlist_command" : "undo_playlist_command",
+                );
+            } catch (error) {
+                console.error("Failed to undo playlist change:", error);
+                showError(error);
+            }
+        },
+        [showError],
+    );
+
+    const handleClearHistory = useCallback(async () => {
+        try {
+            await invoke("clear_play_history");
+        } catch (error) {
+            console.error("Failed to clear play history:", error);
+        }
+    }, []);
+
     // Format duration
     const formatDuration = (duration) => {
         if (!duration) return "";
@@ -151,99 +260,257 @@ const Playlist = memo(() => {
         <div className="playlist">
             <div className="playlist-header">
                 <h3>플레이리스트</h3>
-                <div className="playlist-info">
-                    {playlist.items.length}개 항목
+                <div className="playlist-header-actions">
+                    <label
+                        className="remove-finished-toggle"
+                        title="다음 곡으로 넘어가면 재생이 끝난 곡을 목록에서 지웁니다"
+                    >
+                        <input
+                            type="checkbox"
+                            checked={playlist.remove_finished}
+                            onChange={handleRemoveFinishedToggle}
+                        />
+                        재생 후 삭제
+                    </label>
+                    <label
+                        className="fair-queue-toggle"
+                        title="신청자별로 한 곡씩 번갈아 재생합니다 (직접 옮긴 곡은 자리 고정)"
+                    >
+                        <input
+                            type="checkbox"
+                            checked={playlist.fair_queue}
+                            onChange={handleFairQueueToggle}
+                        />
+                        공평 순서
+                    </label>
+                    <button
+                        className="undo-button"
+                        onClick={() => handleUndo(false)}
+                        title="되돌리기"
+                    >
+                        ↶
+                    </button>
+                    <button
+                        className="undo-button"
+                        onClick={() => handleUndo(true)}
+                        title="다시 실행"
+                    >
+                        ↷
+                    </button>
+                    <button
+                        className={`history-toggle ${showHistory ? "active" : ""}`}
+                        onClick={() => setShowHistory(!showHistory)}
+                    >
+                        재생 기록
+                    </button>
+                    <div className="playlist-info">
+                        {playlist.items.length}개 항목
+                    </div>
                 </div>
             </div>
 
+            <div className="playlist-transfer">
+                <select
+                    value={exportFormat}
+                    onChange={(e) => setExportFormat(e.target.value)}
+                    className="transfer-format-select"
+                >
+                    {TRANSFER_FORMATS.map((format) => (
+                        <option key={format.value} value={format.value}>
+                            {format.label}
+                        </option>
+                    ))}
+                </select>
+                <button className="transfer-button" onClick={handleExport}>
+                    내보내기
+                </button>
+                <button
+                    className="transfer-button"
+                    onClick={() => importInputRef.current?.click()}
+                >
+                    가져오기
+                </button>
+                <input
+                    ref={importInputRef}
+                    type="file"
+                    accept=".json,.m3u,.m3u8,.csv"
+                    onChange={handleImportFile}
+                    hidden
+                />
+            </div>
+
             {errorMessage && (
                 <div className="playlist-error">{errorMessage}</div>
             )}
 
-            <div className="playlist-items">
-                {playlist.items.length === 0 ? (
-                    <div className="playlist-empty">
-                        <p>플레이리스트가 비어있습니다</p>
-                        <p className="playlist-empty-hint">
-                            채팅에서 !playlist 명령어를 사용하여 곡을 추가하세요
-                        </p>
-                    </div>
-                ) : (
-                    playlist.items.map((item, index) => (
-                        <div
-                            key={item.id}
-                            className={`playlist-item ${
-                                index === playlist.current_index ? "active" : ""
-                            } ${dragOverIndex === index ? "drag-over" : ""}`}
-                            draggable
-                            onDragStart={(e) => handleDragStart(e, index)}
-                            onDragEnd={handleDragEnd}
-                            onDragOver={(e) => handleDragOver(e, index)}
-                            onDragLeave={handleDragLeave}
-                            onDrop={(e) => handleDrop(e, index)}
-                            onClick={() => handlePlayItem(index)}
-                        >
-                            <div className="item-index">
-                                {index === playlist.current_index &&
-                                playlist.is_playing ? (
-                                    <span className="playing-icon">▶</span>
-                                ) : (
-                                    <span>{index + 1}</span>
-                                )}
+            {showHistory ? (
+                <div className="playlist-items">
+                    {playlist.history.length === 0 ? (
+                        <div className="playlist-empty">
+                            <p>아직 재생한 곡이 없습니다</p>
+                        </div>
+                    ) : (
+                        [...playlist.history].reverse().map((played, index) => (
+                            <div
+                                key={`${played.item.id}-${played.played_at}-${index}`}
+                                className="playlist-item history-item"
+                            >
+                                <div className="item-info">
+                                    <div className="item-title">
+                                        {played.item.title}
+                                    </div>
+                                    <div className="item-meta">
+                                        <span
+                                            className={`item-added-by ${played.item.added_by === "App User" ? "app-user" : "chat-user"}`}
+                                        >
+                                            {played.item.added_by === "App User"
+                                                ? "👤 직접 추가"
+                                                : `💬 ${played.item.added_by}`}
+                                        </span>
+                                        <span className="meta-separator">•</span>
+                                        <span className="item-added-at">
+                                            {formatTime(played.played_at)} 재생
+                                        </span>
+                                    </div>
+                                </div>
                             </div>
+                        ))
+                    )}
+                </div>
+            ) : (
+                <div className="playlist-items">
+                    {playlist.items.length === 0 ? (
+                        <div className="playlist-empty">
+                            <p>플레이리스트가 비어있습니다</p>
+                            <p className="playlist-empty-hint">
+                                채팅에서 !playlist 명령어를 사용하여 곡을 추가하세요
+                            </p>
+                        </div>
+                    ) : (
+                        playlist.items.map((item, index) => (
+                            <div
+                                key={item.id}
+                                className={`playlist-item ${
+                                    index === playlist.current_index ? "active" : ""
+                                } ${dragOverIndex === index ? "drag-over" : ""}`}
+                                draggable
+                                onDragStart={(e) => handleDragStart(e, index)}
+                                onDragEnd={handleDragEnd}
+                                onDragOver={(e) => handleDragOver(e, index)}
+                                onDragLeave={handleDragLeave}
+                                onDrop={(e) => handleDrop(e, index)}
+                                onClick={() => handlePlayItem(index)}
+                            >
+                                <div className="item-index">
+                                    {index === playlist.current_index &&
+                                    playlist.is_playing ? (
+                                        <span className="playing-icon">▶</span>
+                                    ) : (
+                                        <span>{index + 1}</span>
+                                    )}
+                                </div>
+
+                                {item.thumbnail && (
+                                    <img
+                                        src={item.thumbnail}
+                                        alt={item.title}
+                                        className="item-thumbnail"
+                                    />
+                                )}
 
-                            {item.thumbnail && (
-                                <img
-                                    src={item.thumbnail}
-                                    alt={item.title}
-                                    className="item-thumbnail"
-                                />
-                            )}
-
-                            <div className="item-info">
-                                <div className="item-title">{item.title}</div>
-                                <div className="item-meta">
-                                    <span className="item-channel">
-                                        {item.channel}
-                                    </span>
-                                    {item.duration && (
-                                        <>
-                                            <span className="meta-separator">
-                                                •
+                                <div className="item-info">
+                                    <div className="item-title">
+                                        {item.priority?.source ===
+                                            "donation" && (
+                                            <span className="item-priority donation">
+                                                💰 {item.priority.amount}
                                             </span>
-                                            <span className="item-duration">
-                                                {formatDuration(item.duration)}
+                                        )}
+                                        {item.priority?.source ===
+                                            "subscriber" && (
+                                            <span className="item-priority subscriber">
+                                                ⭐ 구독자
                                             </span>
-                                        </>
-                                    )}
-                                    <span className="meta-separator">•</span>
-                                    <span
-                                        className={`item-added-by ${item.added_by === "App User" ? "app-user" : "chat-user"}`}
-                                    >
-                                        {item.added_by === "App User"
-                                            ? "👤 직접 추가"
-                                            : `💬 ${item.added_by}`}
-                                    </span>
-                                    <span className="meta-separator">•</span>
-                                    <span className="item-added-at">
-                                        {formatTime(item.added_at)}
-                                    </span>
+                                        )}
+                                        {item.source === "fallback" && (
+                                            <span className="item-priority fallback">
+                                                🎵 기본
+                                            </span>
+                                        )}
+                                        {playlist.pinned?.includes(item.id) && (
+                                            <span
+                                                className="item-pinned"
+                                                title="자리 고정됨"
+                                            >
+                                                📌
+                                            </span>
+                                        )}
+                                        {item.title}
+                                    </div>
+                                    <div className="item-meta">
+                                        <span className="item-channel">
+                                            {item.channel}
+                                        </span>
+                                        {item.duration && (
+                                            <>
+                                                <span className="meta-separator">
+                                                    •
+                                                </span>
+                                                <span className="item-duration">
+                                                    {formatDuration(item.duration)}
+                                                </span>
+                                            </>
+                                        )}
+                                        {item.metadata?.is_live && (
+                                            <>
+                                                <span className="meta-separator">
+                                                    •
+                                                </span>
+                                                <span className="item-live">
+                                                    🔴 LIVE
+                                                </span>
+                                            </>
+                                        )}
+                                        <span className="meta-separator">•</span>
+                                        <span
+                                            className={`item-added-by ${item.added_by === "App User" ? "app-user" : "chat-user"}`}
+                                        >
+                                            {item.added_by === "App User"
+                                                ? "👤 직접 추가"
+                                                : `💬 ${item.added_by}`}
+                                        </span>
+                                        <span className="meta-separator">•</span>
+                                        <span className="item-added-at">
+                                            {formatTime(item.added_at)}
+                                        </span>
+                                    </div>
                                 </div>
+
+                                <button
+                                    className="item-remove"
+                                    onClick={(e) => handleRemoveItem(index, e)}
+                                    title="제거"
+                                >
+                                    ✕
+                                </button>
                             </div>
+                        ))
+                    )}
+                </div>
+            )}
 
-                            <button
-                                className="item-remove"
-                                onClick={(e) => handleRemoveItem(index, e)}
-                                title="제거"
-                            >
-                                ✕
-                            </button>
-                        </div>
-                    ))
-                )}
-            </div>
+            {showHistory && playlist.history.length > 0 && (
+                <div className="playlist-controls">
+                    <button
+                        className="playlist-control-button danger"
+                        onClick={handleClearHistory}
+                    >
+                        기록 지우기 🗑️
+                    </button>
+                </div>
+            )}
 
-            {playlist.items.length > 0 && (
+            {!showHistory && playlist.items.length > 0 && (
                 <div className="playlist-controls">
                     <button
                         className="playlist-control-button"
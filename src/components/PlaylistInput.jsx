@@ -120,6 +120,7 @@ const PlaylistInput = memo(() => {
         const newConfig = {
             ...commandConfig,
             playlist_limits: {
+                ...commandConfig.playlist_limits,
                 user_limit: userLimit === "" ? null : parseInt(userLimit, 10),
             },
         };
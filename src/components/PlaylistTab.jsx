@@ -2,6 +2,7 @@ import { memo, useState } from "react";
 import YouTubePlayer from "./YouTubePlayer";
 import Playlist from "./Playlist";
 import PlaylistInput from "./PlaylistInput";
+import FallbackPlaylist from "./FallbackPlaylist";
 import "./PlaylistTab.css";
 
 const PlaylistTab = memo(() => {
@@ -20,6 +21,7 @@ const PlaylistTab = memo(() => {
                     <div className="player-section">
                         <YouTubePlayer />
                         <PlaylistInput />
+                        <FallbackPlaylist />
                     </div>
                 </div>
 
@@ -0,0 +1,199 @@
+import { memo, useState, useEffect, useCallback } from "react";
+
+// 쉼표나 줄바꿈으로 구분된 목록
+const parseList = (text) =>
+    text
+        .split(/[,\n]/)
+        .map((entry) => entry.trim())
+        .filter(Boolean);
+
+const minutesToText = (secs) => (secs == null ? "" : String(secs / 60));
+
+const textToSecs = (text) => (text === "" ? null : parseInt(text, 10) * 60);
+
+const toForm = (limits) => ({
+    maxMinutes: minutesToText(limits.max_duration_secs),
+    minMinutes: minutesToText(limits.min_duration_secs),
+    windowMinutes: String(limits.duplicate_window_mins ?? 0),
+    maxPlaylistImport: String(limits.max_playlist_import ?? 20),
+    region: limits.region ?? "KR",
+    blockedVideoIds: (limits.blocked_video_ids || []).join(", "),
+    blockedChannels: (limits.blocked_channels || []).join(", "),
+    blockedKeywords: (limits.blocked_keywords || []).join(", "),
+});
+
+const RequestPolicyConfig = memo(({ limits, onSave }) => {
+    const [form, setForm] = useState(() => toForm(limits));
+
+    useEffect(() => {
+        setForm(toForm(limits));
+    }, [limits]);
+
+    const handleNumberChange = useCallback((field, value) => {
+        if (value === "" || /^\d+$/.test(value)) {
+            setForm((prev) => ({ ...prev, [field]: value }));
+        }
+    }, []);
+
+    const save = useCallback(
+        (patch = {}) => {
+            onSave({
+                ...limits,
+                max_duration_secs: textToSecs(form.maxMinutes),
+                min_duration_secs: textToSecs(form.minMinutes),
+                duplicate_window_mins: parseInt(form.windowMinutes || "0", 10),
+                max_playlist_import: parseInt(
+                    form.maxPlaylistImport || "0",
+                    10,
+                ),
+                region: form.region.trim().toUpperCase() || "KR",
+                blocked_video_ids: parseList(form.blockedVideoIds),
+                blocked_channels: parseList(form.blockedChannels),
+                blocked_keywords: parseList(form.blockedKeywords),
+                ...patch,
+            });
+        },
+        [form, limits, onSave],
+    );
+
+    return (
+        <div className="user-limit-config">
+            <label className="limit-label">
+                곡 길이 제한
+                <span className="limit-hint">(비워두면 제한 없음)</span>
+            </label>
+            <div className="limit-input-group">
+                <input
+                    type="text"
+                    value={form.minMinutes}
+                    onChange={(e) =>
+                        handleNumberChange("minMinutes", e.target.value)
+                    }
+                    onBlur={() => save()}
+                    placeholder="최소"
+                    className="limit-input"
+                />
+                <span className="limit-suffix">분 ~</span>
+                <input
+                    type="text"
+                    value={form.maxMinutes}
+                    onChange={(e) =>
+                        handleNumberChange("maxMinutes", e.target.value)
+                    }
+                    onBlur={() => save()}
+                    placeholder="최대"
+                    className="limit-input"
+                />
+                <span className="limit-suffix">분</span>
+            </div>
+
+            <label className="permission-months">
+                <input
+                    type="checkbox"
+                    checked={limits.prevent_duplicates}
+                    onChange={() =>
+                        save({ prevent_duplicates: !limits.prevent_duplicates })
+                    }
+                />
+                중복 신청 막기
+            </label>
+            {limits.prevent_duplicates && (
+                <div className="limit-input-group">
+                    <span className="limit-suffix">최근</span>
+                    <input
+                        type="text"
+                        value={form.windowMinutes}
+                        onChange={(e) =>
+                            handleNumberChange("windowMinutes", e.target.value)
+                        }
+                        onBlur={() => save()}
+                        className="limit-input"
+                    />
+                    <span className="limit-suffix">
+                        분 안에 재생된 곡도 거절
+                    </span>
+                </div>
+            )}
+
+            <label className="limit-label">
+                재생목록 링크
+                <span className="limit-hint">(list= 주소)</span>
+            </label>
+            <div className="limit-input-group">
+                <span className="limit-suffix">최대</span>
+                <input
+                    type="text"
+                    value={form.maxPlaylistImport}
+                    onChange={(e) =>
+                        handleNumberChange("maxPlaylistImport", e.target.value)
+                    }
+                    onBlur={() => save()}
+                    className="limit-input"
+                />
+                <span className="limit-suffix">곡까지 추가</span>
+            </div>
+
+            <label className="limit-label">
+                방송 지역
+                <span className="limit-hint">(국가 코드, 예: KR)</span>
+            </label>
+            <div className="limit-input-group">
+                <input
+                    type="text"
+                    value={form.region}
+                    onChange={(e) =>
+                        setForm({ ...form, region: e.target.value })
+                    }
+                    onBlur={() => save()}
+                    maxLength={2}
+                    className="limit-input"
+                />
+                <span className="limit-suffix">
+                    에서 볼 수 없는 영상은 거절
+                </span>
+            </div>
+
+            <label className="limit-label">
+                차단 목록
+                <span className="limit-hint">(쉼표로 구분)</span>
+            </label>
+            <div className="custom-command-form">
+                <input
+                    type="text"
+                    value={form.blockedVideoIds}
+                    onChange={(e) =>
+                        setForm({ ...form, blockedVideoIds: e.target.value })
+                    }
+                    onBlur={() => save()}
+                    placeholder="영상 ID (예: dQw4w9WgXcQ)"
+                />
+                <input
+                    type="text"
+                    value={form.blockedChannels}
+                    onChange={(e) =>
+                        setForm({ ...form, blockedChannels: e.target.value })
+                    }
+                    onBlur={() => save()}
+                    placeholder="채널 이름"
+                />
+                <input
+                    type="text"
+                    value={form.blockedKeywords}
+                    onChange={(e) =>
+                        setForm({ ...form, blockedKeywords: e.target.value })
+                    }
+                    onBlur={() => save()}
+                    placeholder="제목 금지어 (예: 10시간, 1시간 반복)"
+                />
+            </div>
+            <p className="limit-description">
+                규칙에 맞지 않는 신청곡은 거절되고 이유가 표시됩니다. 직접
+                추가한 곡에는 적용되지 않습니다.
+            </p>
+        </div>
+    );
+});
+
+RequestPolicyConfig.displayName = "RequestPolicyConfig";
+
+export default RequestPolicyConfig;
@@ -3,12 +3,21 @@ import { listen } from "@tauri-apps/api/event";
 import { invoke } from "@tauri-apps/api/core";
 import "./YouTubePlayer.css";
 
+const PLAYBACK_MODES = [
+    { value: "no_repeat", label: "반복 없음" },
+    { value: "repeat_all", label: "전체 반복" },
+    { value: "repeat_one", label: "한 곡 반복" },
+    { value: "shuffle", label: "셔플" },
+];
+
 const YouTubePlayer = memo(() => {
     const playerRef = useRef(null);
     const [player, setPlayer] = useState(null);
     const [currentVideo, setCurrentVideo] = useState(null);
     const [isPlaying, setIsPlaying] = useState(false);
     const [autoplay, setAutoplay] = useState(true);
+    const [playbackMode, setPlaybackMode] = useState("no_repeat");
+    const [voteSkip, setVoteSkip] = useState(null);
     const [isApiReady, setIsApiReady] = useState(false);
     const [initError, setInitError] = useState(null);
     const initAttemptRef = useRef(0);
@@ -22,6 +31,7 @@ const YouTubePlayer = memo(() => {
         try {
             const playlist = await invoke("get_playlist");
             setAutoplay(playlist.autoplay);
+            setPlaybackMode(playlist.mode);
         } catch (error) {
             console.error("Failed to load playlist state:", error);
         }
@@ -228,8 +238,8 @@ const YouTubePlayer = memo(() => {
 
             if (playlist.autoplay) {
                 console.log("Autoplay is enabled, playing next video...");
-                // Request next video from backend
-                await invoke("skip_to_next_command");
+                // Request next video from backend (재생 모드에 따라 결정)
+                await invoke("track_ended_command");
             } else {
                 console.log("Autoplay is disabled");
             }
@@ -238,6 +248,17 @@ const YouTubePlayer = memo(() => {
         }
     }, []);
 
+    // Listen for vote skip progress
+    useEffect(() => {
+        const unlisten = listen("voteskip:progress", (event) => {
+            setVoteSkip(event.payload);
+        });
+
+        return async () => {
+            (await unlisten)();
+        };
+    }, []);
+
     // Listen for playlist events
     useEffect(() => {
         const unlistenPlay = listen("playlist:play", (event) => {
@@ -260,6 +281,7 @@ const YouTubePlayer = memo(() => {
         const unlistenUpdated = listen("playlist:updated", (event) => {
             const playlist = event.payload;
             setAutoplay(playlist.autoplay);
+            setPlaybackMode(playlist.mode);
         });
 
         return async () => {
@@ -289,7 +311,7 @@ const YouTubePlayer = memo(() => {
             try {
                 player.loadVideoById({
                     videoId: video.video_id,
-                    startSeconds: 0,
+                    startSeconds: video.start_seconds ?? 0,
                     suggestedQuality: "large",
                 });
             } catch (error) {
@@ -328,6 +350,16 @@ const YouTubePlayer = memo(() => {
         }
     }, [autoplay]);
 
+    const handlePlaybackModeChange = useCallback(async (mode) => {
+        setPlaybackMode(mode);
+
+        try {
+            await invoke("set_playback_mode", { mode });
+        } catch (error) {
+            console.error("Failed to update playback mode:", error);
+        }
+    }, []);
+
     return (
         <div className="youtube-player">
             <div className="player-container">
@@ -375,6 +407,14 @@ const YouTubePlayer = memo(() => {
                         <div className="video-channel">
                             {currentVideo.channel}
                         </div>
+                        {voteSkip &&
+                            !voteSkip.passed &&
+                            voteSkip.item_id === currentVideo.id && (
+                                <div className="vote-skip-progress">
+                                    건너뛰기 투표 {voteSkip.votes}/
+                                    {voteSkip.required}
+                                </div>
+                            )}
                     </div>
 
                     <button
@@ -397,6 +437,21 @@ const YouTubePlayer = memo(() => {
                                 : "재생이 끝나면 정지합니다"}
                         </div>
                     </button>
+
+                    <select
+                        className="playback-mode-select"
+                        value={playbackMode}
+                        onChange={(e) =>
+                            handlePlaybackModeChange(e.target.value)
+                        }
+                        title="재생 모드"
+                    >
+                        {PLAYBACK_MODES.map((mode) => (
+                            <option key={mode.value} value={mode.value}>
+                                {mode.label}
+                            </option>
+                        ))}
+                    </select>
                 </div>
             )}
         </div>